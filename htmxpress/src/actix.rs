@@ -0,0 +1,84 @@
+//! Integration with [actix-web](https://docs.rs/actix-web), enabled with the `actix` feature.
+//!
+//! [HtmxResponse], [Htmx] and elements deriving `Element` with `#[responder]` can be
+//! returned from handlers, and [HxRequest] can be used as an extractor.
+//!
+//! actix-web uses different versions of the `http` types, so the status and headers
+//! are converted when responding. Responses that fail to render are turned into
+//! an empty `500 Internal Server Error`, with the [ResponseError] attached to the
+//! response so that it is reported by actix's `Logger`.
+
+use std::{
+    convert::Infallible,
+    future::{ready, Ready},
+};
+
+use ::actix_web::{
+    dev::Payload,
+    http::{
+        header::{HeaderName, HeaderValue},
+        StatusCode,
+    },
+    FromRequest, ResponseError as ActixResponseError,
+};
+
+use crate::{Htmx, HtmxElement, HtmxResponse, HxRequest, ResponseError};
+
+pub use ::actix_web::{body::BoxBody, HttpRequest, HttpResponse, Responder};
+
+impl Responder for HtmxResponse {
+    type Body = BoxBody;
+
+    fn respond_to(self, _: &HttpRequest) -> HttpResponse<Self::Body> {
+        let response = match self.try_into_http() {
+            Ok(response) => response,
+            Err(e) => return HttpResponse::from_error(e),
+        };
+
+        let (parts, body) = response.into_parts();
+
+        let status = StatusCode::from_u16(parts.status.as_u16())
+            .unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+        let mut actix_response = HttpResponse::with_body(status, body).map_into_boxed_body();
+
+        for (name, value) in parts.headers.iter() {
+            let name = HeaderName::from_bytes(name.as_str().as_bytes());
+            let value = HeaderValue::from_bytes(value.as_bytes());
+            if let (Ok(name), Ok(value)) = (name, value) {
+                actix_response.headers_mut().append(name, value);
+            }
+        }
+
+        actix_response
+    }
+}
+
+impl<T: HtmxElement> Responder for Htmx<T> {
+    type Body = BoxBody;
+
+    fn respond_to(self, req: &HttpRequest) -> HttpResponse<Self::Body> {
+        HtmxResponse::new(&self.0).respond_to(req)
+    }
+}
+
+impl ActixResponseError for ResponseError {
+    fn status_code(&self) -> StatusCode {
+        StatusCode::INTERNAL_SERVER_ERROR
+    }
+
+    fn error_response(&self) -> HttpResponse<BoxBody> {
+        HttpResponse::new(self.status_code())
+    }
+}
+
+impl FromRequest for HxRequest {
+    type Error = Infallible;
+    type Future = Ready<Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, _: &mut Payload) -> Self::Future {
+        let headers = req.headers();
+        ready(Ok(HxRequest::from_lookup(|name| {
+            headers.get(name).map(|value| value.as_bytes())
+        })))
+    }
+}
@@ -0,0 +1,46 @@
+//! Integration with [axum](https://docs.rs/axum), enabled with the `axum` feature.
+//!
+//! [HtmxResponse], [Htmx] and elements deriving `Element` with `#[into_response]` can be
+//! returned from handlers, and [HxRequest] can be used as an extractor.
+//!
+//! Responses that fail to render are turned into an empty `500 Internal Server Error`.
+//! The [ResponseError] is kept in the response's extensions so middleware can log it.
+
+use std::convert::Infallible;
+
+use ::axum::{extract::FromRequestParts, http::request::Parts, http::StatusCode};
+
+use crate::{Htmx, HtmxElement, HtmxResponse, HxRequest, ResponseError};
+
+pub use ::axum::response::{IntoResponse, Response};
+
+impl IntoResponse for HtmxResponse {
+    fn into_response(self) -> Response {
+        match self.try_into_http() {
+            Ok(response) => response.into_response(),
+            Err(e) => e.into_response(),
+        }
+    }
+}
+
+impl<T: HtmxElement> IntoResponse for Htmx<T> {
+    fn into_response(self) -> Response {
+        HtmxResponse::new(&self.0).into_response()
+    }
+}
+
+impl IntoResponse for ResponseError {
+    fn into_response(self) -> Response {
+        let mut response = StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        response.extensions_mut().insert(self);
+        response
+    }
+}
+
+impl<S: Send + Sync> FromRequestParts<S> for HxRequest {
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _: &S) -> Result<Self, Self::Rejection> {
+        Ok(HxRequest::from(&*parts))
+    }
+}
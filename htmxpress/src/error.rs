@@ -0,0 +1,69 @@
+use std::fmt::{self, Display};
+
+/// Error returned when an element fails to render, usually because
+/// a `Display` implementation of one of its fields returned an error.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RenderError {
+    /// Location of the failure, from the outermost element to the innermost
+    path: Vec<RenderLocation>,
+}
+
+/// A single step in the path of a [RenderError].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RenderLocation {
+    /// Name of the type implementing `HtmxElement`
+    pub element: &'static str,
+
+    /// The field being rendered, `None` if the failure happened
+    /// while writing the element's own tags.
+    pub field: Option<&'static str>,
+}
+
+impl RenderError {
+    /// Create an error originating in the given element and field.
+    pub fn new(element: &'static str, field: Option<&'static str>) -> Self {
+        Self {
+            path: vec![RenderLocation { element, field }],
+        }
+    }
+
+    /// Prepend the location of the parent element to the path.
+    pub fn within(mut self, element: &'static str, field: Option<&'static str>) -> Self {
+        self.path.insert(0, RenderLocation { element, field });
+        self
+    }
+
+    /// The path to the failed element, starting from the outermost one.
+    pub fn path(&self) -> &[RenderLocation] {
+        &self.path
+    }
+
+    /// The innermost location, i.e. where the error originated.
+    pub fn origin(&self) -> RenderLocation {
+        self.path[self.path.len() - 1]
+    }
+}
+
+impl Display for RenderLocation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.field {
+            Some(field) => write!(f, "{}.{field}", self.element),
+            None => write!(f, "{}", self.element),
+        }
+    }
+}
+
+impl Display for RenderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "failed to render ")?;
+        for (i, location) in self.path.iter().enumerate() {
+            if i > 0 {
+                write!(f, " > ")?;
+            }
+            write!(f, "{location}")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for RenderError {}
@@ -0,0 +1,115 @@
+//! HTML escaping utilities used by the generated code.
+//!
+//! Values interpolated into element content are wrapped in [Text] and values
+//! interpolated into attributes are wrapped in [Attr]. Both escape the output of the
+//! underlying `Display` implementation while it is being written, so no intermediate
+//! strings are allocated.
+
+use std::fmt::{self, Display, Write};
+
+/// Escapes `&`, `<` and `>` in the display output of the wrapped value,
+/// making it safe to use as the text content of an element.
+pub struct Text<T>(pub T);
+
+/// Escapes `&`, `<`, `>`, `"` and `'` in the display output of the wrapped value,
+/// making it safe to use as the value of a quoted attribute.
+pub struct Attr<T>(pub T);
+
+impl<T: Display> Display for Text<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(Escaper::new(f, Context::Text), "{}", self.0)
+    }
+}
+
+impl<T: Display> Display for Attr<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(Escaper::new(f, Context::Attr), "{}", self.0)
+    }
+}
+
+/// Writes the names of the enabled classes separated by spaces, escaped like [Attr].
+/// Used for elements with conditional classes.
+pub struct ClassList<'a>(pub &'a [(&'a str, bool)]);
+
+impl Display for ClassList<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut escaper = Escaper::new(f, Context::Attr);
+        let mut first = true;
+        for (class, _) in self.0.iter().filter(|(_, enabled)| *enabled) {
+            if !first {
+                escaper.write_str(" ")?;
+            }
+            escaper.write_str(class)?;
+            first = false;
+        }
+        Ok(())
+    }
+}
+
+/// Content that is already valid HTML and will be written as is.
+///
+/// Fields of this type are never escaped by the derive. Use it only
+/// for content that comes from a trusted source.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct PreEscaped<T>(pub T);
+
+/// An owned string of trusted HTML.
+pub type Markup = PreEscaped<String>;
+
+impl<T: Display> Display for PreEscaped<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<T> From<T> for PreEscaped<T> {
+    fn from(value: T) -> Self {
+        Self(value)
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Context {
+    Text,
+    Attr,
+}
+
+impl Context {
+    fn replacement(self, ch: char) -> Option<&'static str> {
+        match (self, ch) {
+            (_, '&') => Some("&amp;"),
+            (_, '<') => Some("&lt;"),
+            (_, '>') => Some("&gt;"),
+            (Context::Attr, '"') => Some("&quot;"),
+            (Context::Attr, '\'') => Some("&#39;"),
+            _ => None,
+        }
+    }
+}
+
+/// Writer adapter escaping everything written to it.
+struct Escaper<'a, W: Write + ?Sized> {
+    inner: &'a mut W,
+    context: Context,
+}
+
+impl<'a, W: Write + ?Sized> Escaper<'a, W> {
+    fn new(inner: &'a mut W, context: Context) -> Self {
+        Self { inner, context }
+    }
+}
+
+impl<W: Write + ?Sized> Write for Escaper<'_, W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let mut last = 0;
+        for (i, ch) in s.char_indices() {
+            let Some(replacement) = self.context.replacement(ch) else {
+                continue;
+            };
+            self.inner.write_str(&s[last..i])?;
+            self.inner.write_str(replacement)?;
+            last = i + ch.len_utf8();
+        }
+        self.inner.write_str(&s[last..])
+    }
+}
@@ -0,0 +1,294 @@
+use std::fmt;
+
+use crate::{Head, HtmxElement, OobSwap, RenderError};
+
+/// Types rendered as a `<select>` in `#[form]` elements, with one `<option>` per value.
+///
+/// Usually derived on enums with unit variants, using serde's `rename` and `rename_all`
+/// for the values so they deserialize into the same variant. Labels default to the
+/// variant names split into words and can be set with `#[label("...")]`.
+///
+/// ```
+/// use htmxpress::Select;
+///
+/// #[derive(Select)]
+/// enum Status {
+///     Open,
+///     InProgress,
+///     #[label("Done!")]
+///     Closed,
+/// }
+///
+/// assert_eq!(
+///     &[("Open", "Open"), ("InProgress", "In progress"), ("Closed", "Done!")],
+///     Status::options()
+/// );
+/// assert_eq!("InProgress", Status::InProgress.value());
+/// ```
+pub trait Select {
+    /// The value and label of every option
+    fn options() -> &'static [(&'static str, &'static str)];
+
+    /// The value of the option representing `self`
+    fn value(&self) -> &'static str;
+}
+
+/// Errors of a submitted form, rendered next to the controls they belong to with [Form::with_errors].
+///
+/// Errors are keyed by the name of the control, i.e. the field name after any serde renames.
+/// Each control has at most one error, adding another one replaces it.
+///
+/// ```
+/// use htmxpress::ValidationErrors;
+///
+/// let errors = ValidationErrors::new()
+///     .with("email", "Invalid email")
+///     .with("age", "Too young")
+///     .with("age", "Too old");
+///
+/// assert_eq!(Some("Too old"), errors.get("age"));
+/// assert_eq!(2, errors.len());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ValidationErrors {
+    errors: Vec<(String, String)>,
+}
+
+impl ValidationErrors {
+    pub const fn new() -> Self {
+        Self { errors: vec![] }
+    }
+
+    /// Set the error of the control with the given name.
+    pub fn add(&mut self, name: impl Into<String>, message: impl Into<String>) {
+        let name = name.into();
+        let message = message.into();
+
+        match self.errors.iter_mut().find(|(n, _)| *n == name) {
+            Some((_, m)) => *m = message,
+            None => self.errors.push((name, message)),
+        }
+    }
+
+    /// Same as [ValidationErrors::add], for chaining.
+    pub fn with(mut self, name: impl Into<String>, message: impl Into<String>) -> Self {
+        self.add(name, message);
+        self
+    }
+
+    /// The error of the control with the given name.
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.errors
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, message)| message.as_str())
+    }
+
+    /// The names of the controls and their errors, in the order they were added.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.errors
+            .iter()
+            .map(|(name, message)| (name.as_str(), message.as_str()))
+    }
+
+    pub fn len(&self) -> usize {
+        self.errors.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.errors.is_empty()
+    }
+}
+
+/// Implemented by elements annotated with `#[form]`, rendering their controls along with
+/// any validation errors.
+///
+/// Controls with an error get `aria-invalid="true"` and `aria-describedby` referencing
+/// the message, written after the control as `<span id="{name}-error" class="error">`.
+pub trait Form: HtmxElement {
+    /// Write the form with the errors next to their controls.
+    fn try_write_form(
+        &self,
+        html: &mut impl fmt::Write,
+        errors: &ValidationErrors,
+    ) -> Result<(), RenderError>;
+
+    /// Write the form with the errors next to their controls as an out of band swap,
+    /// see [HtmxElement::try_write_htmx_oob].
+    fn try_write_form_oob(
+        &self,
+        html: &mut impl fmt::Write,
+        swap: &OobSwap,
+        errors: &ValidationErrors,
+    ) -> Result<(), RenderError>;
+
+    /// Write only the element whose `id` attribute equals `id` with the errors next to
+    /// their controls, see [HtmxElement::try_write_htmx_fragment].
+    fn try_write_form_fragment(
+        &self,
+        id: &str,
+        html: &mut impl fmt::Write,
+        errors: &ValidationErrors,
+    ) -> Result<bool, RenderError> {
+        let _ = (id, html, errors);
+        Ok(false)
+    }
+
+    /// Render the form with the errors when used as an [HtmxElement], e.g. in a response.
+    fn with_errors<'a>(&'a self, errors: &'a ValidationErrors) -> WithErrors<'a, Self>
+    where
+        Self: Sized,
+    {
+        WithErrors { form: self, errors }
+    }
+}
+
+/// A form rendered along with its validation errors, see [Form::with_errors].
+#[derive(Debug)]
+pub struct WithErrors<'a, F> {
+    form: &'a F,
+    errors: &'a ValidationErrors,
+}
+
+impl<F: Form> HtmxElement for WithErrors<'_, F> {
+    fn try_write_htmx(&self, html: &mut impl fmt::Write) -> Result<(), RenderError> {
+        self.form.try_write_form(html, self.errors)
+    }
+
+    fn try_write_htmx_oob(
+        &self,
+        html: &mut impl fmt::Write,
+        swap: &OobSwap,
+    ) -> Result<(), RenderError> {
+        self.form.try_write_form_oob(html, swap, self.errors)
+    }
+
+    fn try_write_htmx_fragment(
+        &self,
+        id: &str,
+        html: &mut impl fmt::Write,
+    ) -> Result<bool, RenderError> {
+        self.form.try_write_form_fragment(id, html, self.errors)
+    }
+
+    fn collect_head(&self, head: &mut Head) {
+        self.form.collect_head(head)
+    }
+
+    fn size_hint(&self) -> usize {
+        self.form.size_hint()
+    }
+}
+
+/// Deserialize an optional form control, treating an empty value as `None`.
+///
+/// Browsers submit empty controls, such as a number input without a value or the empty
+/// option of an optional select, as an empty string. `#[form]` elements require it on their
+/// optional controls, used as `#[serde(default, deserialize_with = "htmxpress::empty_as_none")]`.
+///
+/// ```
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize)]
+/// struct Filter {
+///     #[serde(default, deserialize_with = "htmxpress::empty_as_none")]
+///     page: Option<u32>,
+/// }
+///
+/// let filter: Filter = serde_urlencoded::from_str("page=").unwrap();
+/// assert_eq!(None, filter.page);
+///
+/// let filter: Filter = serde_urlencoded::from_str("page=2").unwrap();
+/// assert_eq!(Some(2), filter.page);
+/// ```
+#[cfg(feature = "serde")]
+pub fn empty_as_none<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: serde::Deserialize<'de>,
+{
+    use serde::{de::Error, Deserialize};
+
+    match Option::<String>::deserialize(deserializer)? {
+        Some(value) if !value.is_empty() => T::deserialize(de::FormValue(value))
+            .map(Some)
+            .map_err(D::Error::custom),
+        _ => Ok(None),
+    }
+}
+
+#[cfg(feature = "serde")]
+mod de {
+    use serde::{
+        de::{value, Error, IntoDeserializer, Unexpected, Visitor},
+        forward_to_deserialize_any, Deserializer,
+    };
+
+    /// A single submitted value, parsed into numbers and booleans on demand
+    pub struct FormValue(pub String);
+
+    macro_rules! parse {
+        ($($method:ident => $visit:ident,)*) => {
+            $(
+                fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+                    match self.0.parse() {
+                        Ok(value) => visitor.$visit(value),
+                        Err(_) => Err(Error::invalid_value(Unexpected::Str(&self.0), &visitor)),
+                    }
+                }
+            )*
+        };
+    }
+
+    impl<'de> Deserializer<'de> for FormValue {
+        type Error = value::Error;
+
+        fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+            visitor.visit_string(self.0)
+        }
+
+        fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+            visitor.visit_some(self)
+        }
+
+        fn deserialize_newtype_struct<V: Visitor<'de>>(
+            self,
+            _: &'static str,
+            visitor: V,
+        ) -> Result<V::Value, Self::Error> {
+            visitor.visit_newtype_struct(self)
+        }
+
+        fn deserialize_enum<V: Visitor<'de>>(
+            self,
+            name: &'static str,
+            variants: &'static [&'static str],
+            visitor: V,
+        ) -> Result<V::Value, Self::Error> {
+            let value: value::StringDeserializer<Self::Error> = self.0.into_deserializer();
+            value.deserialize_enum(name, variants, visitor)
+        }
+
+        parse! {
+            deserialize_bool => visit_bool,
+            deserialize_i8 => visit_i8,
+            deserialize_i16 => visit_i16,
+            deserialize_i32 => visit_i32,
+            deserialize_i64 => visit_i64,
+            deserialize_i128 => visit_i128,
+            deserialize_u8 => visit_u8,
+            deserialize_u16 => visit_u16,
+            deserialize_u32 => visit_u32,
+            deserialize_u64 => visit_u64,
+            deserialize_u128 => visit_u128,
+            deserialize_f32 => visit_f32,
+            deserialize_f64 => visit_f64,
+            deserialize_char => visit_char,
+        }
+
+        forward_to_deserialize_any! {
+            str string bytes byte_buf unit unit_struct seq tuple
+            tuple_struct map struct identifier ignored_any
+        }
+    }
+}
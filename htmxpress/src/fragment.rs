@@ -0,0 +1,40 @@
+//! Utilities for finding fragments by their `id`, used by the generated code.
+
+use std::fmt::{self, Write};
+
+/// Checks whether the formatted output equals the given `id` without allocating.
+///
+/// Writing fails as soon as the output diverges from the `id`.
+pub struct IdMatcher<'a> {
+    remaining: &'a str,
+    matches: bool,
+}
+
+impl<'a> IdMatcher<'a> {
+    pub fn new(id: &'a str) -> Self {
+        Self {
+            remaining: id,
+            matches: true,
+        }
+    }
+
+    /// Whether everything written so far equals the whole `id`
+    pub fn matches(&self) -> bool {
+        self.matches && self.remaining.is_empty()
+    }
+}
+
+impl Write for IdMatcher<'_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        match self.remaining.strip_prefix(s) {
+            Some(remaining) if self.matches => {
+                self.remaining = remaining;
+                Ok(())
+            }
+            _ => {
+                self.matches = false;
+                Err(fmt::Error)
+            }
+        }
+    }
+}
@@ -0,0 +1,130 @@
+//! Elements of `<head>` declared by components, for use with the htmx head-support extension.
+
+use std::fmt::Write;
+
+use crate::{
+    escape::{Attr, Text},
+    HtmxElement, RenderError,
+};
+
+/// An element of `<head>` declared by a component with `#[head(...)]`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HeadItem {
+    /// `<title>`
+    Title(&'static str),
+
+    /// `<meta>` with the given attributes
+    Meta(&'static [(&'static str, &'static str)]),
+
+    /// `<link rel="stylesheet">` with the given `href`
+    Stylesheet(&'static str),
+
+    /// `<script>` with the given `src`
+    Script(&'static str),
+}
+
+impl HeadItem {
+    fn try_write(&self, out: &mut impl Write) -> std::fmt::Result {
+        match self {
+            HeadItem::Title(title) => write!(out, "<title>{}</title>", Text(title)),
+            HeadItem::Meta(attrs) => {
+                out.write_str("<meta")?;
+                for (key, value) in attrs.iter() {
+                    write!(out, r#" {key}="{}""#, Attr(value))?;
+                }
+                out.write_str(">")
+            }
+            HeadItem::Stylesheet(href) => {
+                write!(out, r#"<link rel="stylesheet" href="{}">"#, Attr(href))
+            }
+            HeadItem::Script(src) => write!(out, r#"<script src="{}"></script>"#, Attr(src)),
+        }
+    }
+
+    /// Whether both items would occupy the same place in `<head>`,
+    /// in which case only the first one is kept
+    fn replaces(&self, other: &HeadItem) -> bool {
+        match (self, other) {
+            (HeadItem::Title(_), HeadItem::Title(_)) => true,
+            (HeadItem::Meta(a), HeadItem::Meta(b)) => match (meta_key(a), meta_key(b)) {
+                (Some(a), Some(b)) => a == b || (a.0 == "charset" && b.0 == "charset"),
+                _ => a == b,
+            },
+            (a, b) => a == b,
+        }
+    }
+}
+
+/// The attribute identifying what a `<meta>` is about
+fn meta_key(attrs: &[(&'static str, &'static str)]) -> Option<(&'static str, &'static str)> {
+    attrs
+        .iter()
+        .find(|(key, _)| ["name", "property", "http-equiv", "charset"].contains(key))
+        .copied()
+}
+
+/// The `<head>` elements collected from a component and all of its nested components.
+///
+/// Items are deduplicated as they are collected, keeping the first one. Only one title is kept,
+/// as is only one `<meta>` per `name`, `property`, `http-equiv` or `charset`. Since components
+/// are collected before their children, the outermost component takes precedence.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Head {
+    items: Vec<HeadItem>,
+}
+
+impl Head {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Collect the head items of the element and its nested elements.
+    pub fn collect(element: &impl HtmxElement) -> Self {
+        let mut head = Self::new();
+        element.collect_head(&mut head);
+        head
+    }
+
+    /// Add the item unless an equivalent one was already added.
+    pub fn push(&mut self, item: HeadItem) {
+        if !self.items.iter().any(|existing| existing.replaces(&item)) {
+            self.items.push(item);
+        }
+    }
+
+    pub fn items(&self) -> &[HeadItem] {
+        &self.items
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Write the items wrapped in `<head hx-head="merge">`, which the head-support extension
+    /// merges into the current head when swapping partial responses. Writes nothing if empty.
+    pub fn try_write_merge(&self, out: &mut impl Write) -> Result<(), RenderError> {
+        if self.is_empty() {
+            return Ok(());
+        }
+
+        let error = |_| RenderError::new("Head", None);
+        out.write_str(r#"<head hx-head="merge">"#).map_err(error)?;
+        self.try_write_htmx(out)?;
+        out.write_str("</head>").map_err(error)
+    }
+}
+
+/// Writes the items without a surrounding `<head>`.
+impl HtmxElement for Head {
+    fn try_write_htmx(&self, out: &mut impl Write) -> Result<(), RenderError> {
+        for item in self.items.iter() {
+            item.try_write(out)
+                .map_err(|_| RenderError::new("Head", None))?;
+        }
+        Ok(())
+    }
+
+    fn size_hint(&self) -> usize {
+        self.items.len() * 48
+    }
+}
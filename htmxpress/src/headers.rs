@@ -0,0 +1,59 @@
+//! Names of the headers used by htmx.
+
+use http::HeaderName;
+
+/// Request header, always `true` for requests made by htmx.
+pub const HX_REQUEST: HeaderName = HeaderName::from_static("hx-request");
+
+/// Request header, `true` if the request is via an element using `hx-boost`.
+pub const HX_BOOSTED: HeaderName = HeaderName::from_static("hx-boosted");
+
+/// Request header, the current URL of the browser.
+pub const HX_CURRENT_URL: HeaderName = HeaderName::from_static("hx-current-url");
+
+/// Request header, `true` if the request is for history restoration after a miss in the local history cache.
+pub const HX_HISTORY_RESTORE_REQUEST: HeaderName =
+    HeaderName::from_static("hx-history-restore-request");
+
+/// Request header, the user response to an `hx-prompt`.
+pub const HX_PROMPT: HeaderName = HeaderName::from_static("hx-prompt");
+
+/// Request header, the id of the target element if it exists.
+pub const HX_TARGET: HeaderName = HeaderName::from_static("hx-target");
+
+/// Request header, the id of the triggered element if it exists.
+/// Response header, triggers client side events.
+pub const HX_TRIGGER: HeaderName = HeaderName::from_static("hx-trigger");
+
+/// Request header, the name of the triggered element if it exists.
+pub const HX_TRIGGER_NAME: HeaderName = HeaderName::from_static("hx-trigger-name");
+
+/// Response header, triggers client side events after the swap step.
+pub const HX_TRIGGER_AFTER_SWAP: HeaderName = HeaderName::from_static("hx-trigger-after-swap");
+
+/// Response header, triggers client side events after the settle step.
+pub const HX_TRIGGER_AFTER_SETTLE: HeaderName = HeaderName::from_static("hx-trigger-after-settle");
+
+/// Response header, pushes a new url into the history stack.
+pub const HX_PUSH_URL: HeaderName = HeaderName::from_static("hx-push-url");
+
+/// Response header, replaces the current URL in the location bar.
+pub const HX_REPLACE_URL: HeaderName = HeaderName::from_static("hx-replace-url");
+
+/// Response header, performs a client side redirect that does a full page reload.
+pub const HX_REDIRECT: HeaderName = HeaderName::from_static("hx-redirect");
+
+/// Response header, if `true` the client side will do a full refresh of the page.
+pub const HX_REFRESH: HeaderName = HeaderName::from_static("hx-refresh");
+
+/// Response header, performs a client side redirect that does not do a full page reload.
+pub const HX_LOCATION: HeaderName = HeaderName::from_static("hx-location");
+
+/// Response header, a CSS selector that updates the target of the content update.
+pub const HX_RETARGET: HeaderName = HeaderName::from_static("hx-retarget");
+
+/// Response header, specifies how the response will be swapped.
+pub const HX_RESWAP: HeaderName = HeaderName::from_static("hx-reswap");
+
+/// Response header, a CSS selector that allows choosing which part of the response is used to be swapped in.
+pub const HX_RESELECT: HeaderName = HeaderName::from_static("hx-reselect");
@@ -0,0 +1,97 @@
+//! JSON writing for htmx header values and the `hx-vals` and `hx-headers` attributes.
+//!
+//! Header values are written without any dependencies, with all non ASCII characters escaped
+//! so the output is always a valid header value. Serializing arbitrary values with
+//! [Json] requires the `serde` feature.
+
+use std::fmt::Write;
+
+/// Write `value` as a JSON string literal, quotes included
+pub(crate) fn write_str(out: &mut String, value: &str) {
+    out.push('"');
+    for ch in value.chars() {
+        match ch {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            ch if ch.is_ascii() && !ch.is_ascii_control() => out.push(ch),
+            ch => {
+                let mut buf = [0; 2];
+                for unit in ch.encode_utf16(&mut buf) {
+                    let _ = write!(out, "\\u{unit:04x}");
+                }
+            }
+        }
+    }
+    out.push('"');
+}
+
+/// Write the key value pairs as a JSON object with string values
+pub(crate) fn write_object<'a>(
+    out: &mut String,
+    entries: impl IntoIterator<Item = (&'a str, &'a str)>,
+) {
+    out.push('{');
+    for (i, (key, value)) in entries.into_iter().enumerate() {
+        if i > 0 {
+            out.push(',');
+        }
+        write_str(out, key);
+        out.push(':');
+        write_str(out, value);
+    }
+    out.push('}');
+}
+
+/// Serializes the wrapped value to JSON when displayed, used by `#[hx_vals(serde = field)]`
+/// and `#[hx_headers(serde = field)]`.
+///
+/// Serialization errors are reported as `fmt::Error`. The output is not escaped, wrap it in
+/// [Attr](crate::escape::Attr) when writing it to an attribute.
+#[cfg(feature = "serde")]
+pub struct Json<T>(pub T);
+
+#[cfg(feature = "serde")]
+impl<T: serde::Serialize> std::fmt::Display for Json<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let json = serde_json::to_string(&self.0).map_err(|_| std::fmt::Error)?;
+        f.write_str(&json)
+    }
+}
+
+/// A JSON object with the given keys and values, used by `#[hx_vals(a, b)]`
+/// and `#[hx_headers(a, b)]`. Display it with [Json].
+#[cfg(feature = "serde")]
+pub struct JsonObject<'a>(pub &'a [(&'a str, &'a dyn erased::Serialize)]);
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for JsonObject<'_> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::{Error, SerializeMap};
+
+        let mut map = serializer.serialize_map(Some(self.0.len()))?;
+        for (key, value) in self.0 {
+            let value = value.to_value().map_err(S::Error::custom)?;
+            map.serialize_entry(key, &value)?;
+        }
+        map.end()
+    }
+}
+
+#[cfg(feature = "serde")]
+pub mod erased {
+    //! Object safe serialization, allowing fields of different types in a [JsonObject](super::JsonObject).
+
+    /// Implemented for all `serde::Serialize` types.
+    pub trait Serialize {
+        fn to_value(&self) -> serde_json::Result<serde_json::Value>;
+    }
+
+    impl<T: serde::Serialize + ?Sized> Serialize for T {
+        fn to_value(&self) -> serde_json::Result<serde_json::Value> {
+            serde_json::to_value(self)
+        }
+    }
+}
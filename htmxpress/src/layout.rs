@@ -0,0 +1,173 @@
+//! Rendering elements as full HTML pages or as bare fragments, depending on the request.
+
+use std::fmt::Write;
+
+use http::{header::VARY, HeaderValue};
+
+use crate::{escape::Attr, Head, HtmxElement, HtmxResponse, HxRequest, RenderError};
+
+/// The document surrounding the elements rendered as a full page with [Page].
+pub trait Layout {
+    /// Write the content of `<head>`, e.g. the title, stylesheets and scripts.
+    fn try_write_head(&self, out: &mut impl Write) -> Result<(), RenderError>;
+
+    /// Write the content of `<body>`, containing the element of the page.
+    /// Override to add content shared by all pages, such as navigation.
+    fn try_write_body(
+        &self,
+        content: &impl HtmxElement,
+        out: &mut impl Write,
+    ) -> Result<(), RenderError> {
+        content.try_write_htmx(out)
+    }
+
+    /// Add `<head>` items to the ones collected from the element of the page.
+    ///
+    /// Items are collected from the element first, so the ones from the layout act as defaults,
+    /// e.g. a title added here is only used if the element does not declare one.
+    fn collect_head(&self, head: &mut Head) {
+        let _ = head;
+    }
+
+    /// The `lang` attribute of `<html>`.
+    fn lang(&self) -> Option<&str> {
+        None
+    }
+}
+
+/// An element rendered either as a full page using a [Layout] or as a bare fragment.
+///
+/// Rendering the page with [HtmxElement] methods always renders the full page,
+/// while [Page::response] chooses based on the request.
+///
+/// The `<head>` items declared by the element, its nested elements and the layout are written
+/// after the content from [Layout::try_write_head] on full pages. Partial responses start with
+/// the items wrapped in `<head hx-head="merge">` for the head-support extension. Content written
+/// by [Layout::try_write_head] is not part of it and should use `hx-preserve` to be kept.
+///
+/// ```
+/// use htmxpress::{Element, HtmxElement, HxRequest, Layout, Page, RenderError};
+/// use http::{HeaderMap, HeaderValue};
+///
+/// #[derive(Element)]
+/// struct Head {
+///     #[element("title")]
+///     title: &'static str,
+/// }
+///
+/// struct Base;
+///
+/// impl Layout for Base {
+///     fn try_write_head(&self, out: &mut impl std::fmt::Write) -> Result<(), RenderError> {
+///         Head { title: "Todos" }.try_write_htmx(out)
+///     }
+/// }
+///
+/// #[derive(Element)]
+/// #[element("p")]
+/// struct Message {
+///     #[element("span")]
+///     text: &'static str,
+/// }
+///
+/// let page = Page::new(Base, Message { text: "Hello" });
+///
+/// let response = page.response(&HxRequest::default()).try_into_http().unwrap();
+/// assert_eq!(
+///     concat!(
+///         "<!DOCTYPE html><html><head><title>Todos</title></head>",
+///         "<body><p><span>Hello</span></p></body></html>"
+///     ),
+///     response.body()
+/// );
+///
+/// let mut headers = HeaderMap::new();
+/// headers.insert("hx-request", HeaderValue::from_static("true"));
+///
+/// let response = page.response(&HxRequest::from(&headers)).try_into_http().unwrap();
+/// assert_eq!("<p><span>Hello</span></p>", response.body());
+/// assert_eq!("HX-Request", response.headers()["vary"]);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Page<L, E> {
+    pub layout: L,
+    pub content: E,
+}
+
+impl<L: Layout, E: HtmxElement> Page<L, E> {
+    pub fn new(layout: L, content: E) -> Self {
+        Self { layout, content }
+    }
+
+    /// Whether a full page should be rendered for the request, which is the case for requests
+    /// not made by htmx and for history restoration after a miss in the local history cache.
+    pub fn is_full_page(hx: &HxRequest) -> bool {
+        !hx.is_htmx() || hx.is_history_restore_request()
+    }
+
+    /// Create a response with the full page or only the element, see [Page::is_full_page].
+    ///
+    /// Sets `Vary: HX-Request` so caches keep the two versions apart.
+    pub fn response(&self, hx: &HxRequest) -> HtmxResponse {
+        let response = if Self::is_full_page(hx) {
+            HtmxResponse::new(self)
+        } else {
+            HtmxResponse::new(&Partial(self))
+        };
+
+        response.header(VARY, HeaderValue::from_static("HX-Request"))
+    }
+}
+
+impl<L: Layout, E: HtmxElement> HtmxElement for Page<L, E> {
+    fn try_write_htmx(&self, out: &mut impl Write) -> Result<(), RenderError> {
+        let error = || RenderError::new("Page", None);
+
+        out.write_str("<!DOCTYPE html><html").map_err(|_| error())?;
+        if let Some(lang) = self.layout.lang() {
+            write!(out, r#" lang="{}""#, Attr(lang)).map_err(|_| error())?;
+        }
+        out.write_str("><head>").map_err(|_| error())?;
+        self.layout
+            .try_write_head(out)
+            .map_err(|e| e.within("Page", Some("layout")))?;
+        Head::collect(self)
+            .try_write_htmx(out)
+            .map_err(|e| e.within("Page", None))?;
+        out.write_str("</head><body>").map_err(|_| error())?;
+        self.layout
+            .try_write_body(&self.content, out)
+            .map_err(|e| e.within("Page", Some("content")))?;
+        out.write_str("</body></html>").map_err(|_| error())
+    }
+
+    fn size_hint(&self) -> usize {
+        "<!DOCTYPE html><html><head></head><body></body></html>".len() + self.content.size_hint()
+    }
+
+    fn try_write_htmx_fragment(&self, id: &str, out: &mut impl Write) -> Result<bool, RenderError> {
+        self.content.try_write_htmx_fragment(id, out)
+    }
+
+    fn collect_head(&self, head: &mut Head) {
+        self.content.collect_head(head);
+        self.layout.collect_head(head);
+    }
+}
+
+/// The element of a page in a partial response, preceded by the `<head>` items of the page.
+///
+/// Items from both the element and the layout are included since merging
+/// removes the ones missing from the response.
+struct Partial<'a, L, E>(&'a Page<L, E>);
+
+impl<L: Layout, E: HtmxElement> HtmxElement for Partial<'_, L, E> {
+    fn try_write_htmx(&self, out: &mut impl Write) -> Result<(), RenderError> {
+        Head::collect(self.0).try_write_merge(out)?;
+        self.0.content.try_write_htmx(out)
+    }
+
+    fn size_hint(&self) -> usize {
+        self.0.content.size_hint()
+    }
+}
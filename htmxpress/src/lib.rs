@@ -1,12 +1,139 @@
 #![doc = include_str!(concat!("../", std::env!("CARGO_PKG_README")))]
 
-pub use htmxpress_macros::Element;
+pub use htmxpress_macros::{Element, Select};
+
+pub mod escape;
+pub mod fragment;
+pub mod headers;
+pub mod json;
+
+mod error;
+mod form;
+mod head;
+mod layout;
+mod oob;
+mod request;
+mod response;
+mod swap;
+mod trigger;
+
+pub use error::{RenderError, RenderLocation};
+pub use escape::{Markup, PreEscaped};
+pub use form::{Form, Select, ValidationErrors, WithErrors};
+
+#[cfg(feature = "serde")]
+pub use form::empty_as_none;
+pub use head::{Head, HeadItem};
+pub use layout::{Layout, Page};
+pub use oob::{Oob, OobSwap};
+pub use request::HxRequest;
+pub use response::{Htmx, HtmxResponse, HxEvent, HxLocation, ResponseError};
+pub use swap::{HxSwap, Scroll, ScrollPosition, SwapStyle};
+pub use trigger::{HxTrigger, TriggerQueue};
 
 #[cfg(feature = "urlencoding")]
 pub use urlencoding;
 
+#[cfg(feature = "serde")]
+pub use serde_json;
+
+#[cfg(feature = "axum")]
+pub mod axum;
+
+#[cfg(feature = "actix")]
+pub mod actix;
+
 pub trait HtmxElement {
-    fn to_htmx(&self) -> String;
+    /// Write the HTML of this element to the given writer, reporting
+    /// which element and field failed to render on error.
+    ///
+    /// Nested elements write to the same writer, so rendering a whole
+    /// component tree requires no intermediate allocations.
+    fn try_write_htmx(&self, out: &mut impl std::fmt::Write) -> Result<(), RenderError>;
+
+    /// Write the HTML of this element to the given writer.
+    fn write_htmx(&self, out: &mut impl std::fmt::Write) -> std::fmt::Result {
+        self.try_write_htmx(out).map_err(|_| std::fmt::Error)
+    }
+
+    /// Write the HTML of this element as an out of band swap, adding
+    /// `hx-swap-oob` to its root element.
+    ///
+    /// Implemented by the derive. Elements without a root element cannot
+    /// be swapped out of band and fail to render.
+    fn try_write_htmx_oob(
+        &self,
+        out: &mut impl std::fmt::Write,
+        swap: &OobSwap,
+    ) -> Result<(), RenderError> {
+        let _ = (out, swap);
+        Err(RenderError::new(std::any::type_name::<Self>(), None))
+    }
+
+    /// Write only the element whose `id` attribute equals `id`, returning whether it was found.
+    ///
+    /// The element itself is checked first, followed by its fields, nested elements and
+    /// the items of nested lists, in declaration order. Nothing is written if no element matches.
+    fn try_write_htmx_fragment(
+        &self,
+        id: &str,
+        out: &mut impl std::fmt::Write,
+    ) -> Result<bool, RenderError> {
+        let _ = (id, out);
+        Ok(false)
+    }
+
+    /// Render only the element whose `id` attribute equals `id` to a new `String`.
+    /// See [HtmxElement::try_write_htmx_fragment].
+    fn try_to_htmx_fragment(&self, id: &str) -> Result<Option<String>, RenderError> {
+        let mut html = String::new();
+        let found = self.try_write_htmx_fragment(id, &mut html)?;
+        Ok(found.then_some(html))
+    }
+
+    /// Render only the element whose `id` attribute equals `id` to a new `String`,
+    /// returning `None` if there is no such element or it fails to render.
+    fn to_htmx_fragment(&self, id: &str) -> Option<String> {
+        self.try_to_htmx_fragment(id).ok().flatten()
+    }
+
+    /// Add the `<head>` items declared with `#[head(...)]` by this element
+    /// and its nested elements to `head`.
+    fn collect_head(&self, head: &mut Head) {
+        let _ = head;
+    }
+
+    /// An estimate of the length of the rendered HTML, used to
+    /// preallocate the output when rendering to a `String`.
+    fn size_hint(&self) -> usize {
+        0
+    }
+
+    /// Render the HTML of this element to a new `String`.
+    fn try_to_htmx(&self) -> Result<String, RenderError> {
+        let mut html = String::with_capacity(self.size_hint());
+        self.try_write_htmx(&mut html)?;
+        Ok(html)
+    }
+
+    /// Render the HTML of this element to a new `String`.
+    ///
+    /// If rendering fails, the output is truncated at the point of failure.
+    /// Use [HtmxElement::try_to_htmx] to handle the error.
+    fn to_htmx(&self) -> String {
+        let mut html = String::with_capacity(self.size_hint());
+        let _ = self.write_htmx(&mut html);
+        html
+    }
+
+    /// Render this element into an [HtmxResponse], which can be used
+    /// to set htmx response headers.
+    fn htmx_response(&self) -> HtmxResponse
+    where
+        Self: Sized,
+    {
+        HtmxResponse::new(self)
+    }
 
     fn to_htmx_response(&self) -> http::Response<String> {
         let htmx = self.to_htmx();
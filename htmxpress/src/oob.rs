@@ -0,0 +1,90 @@
+//! Out of band swaps, for updating multiple parts of the page with a single response.
+
+use std::fmt::{self, Display, Write};
+
+use crate::{Head, HtmxElement, RenderError, SwapStyle};
+
+/// The value of the `hx-swap-oob` attribute written to the root element of an out of band swap.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum OobSwap {
+    /// `true`, replaces the element with the same `id`
+    #[default]
+    True,
+
+    /// Swaps the response into the element with the same `id` using the style,
+    /// e.g. `innerHTML`
+    Style(SwapStyle),
+
+    /// Swaps the response into the elements matching the CSS selector using the style,
+    /// e.g. `beforeend:#list`
+    Target(SwapStyle, String),
+}
+
+impl OobSwap {
+    /// Swap into the elements matching the CSS selector using the style.
+    pub fn target(style: SwapStyle, selector: impl Into<String>) -> Self {
+        Self::Target(style, selector.into())
+    }
+}
+
+impl Display for OobSwap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OobSwap::True => f.write_str("true"),
+            OobSwap::Style(style) => write!(f, "{style}"),
+            OobSwap::Target(style, selector) => write!(f, "{style}:{selector}"),
+        }
+    }
+}
+
+impl From<SwapStyle> for OobSwap {
+    fn from(style: SwapStyle) -> Self {
+        Self::Style(style)
+    }
+}
+
+/// Renders the wrapped element as an out of band swap.
+///
+/// Useful for nesting out of band swaps in other elements, or for
+/// rendering them on their own.
+///
+/// ```
+/// use htmxpress::{Element, HtmxElement, Oob, OobSwap, SwapStyle};
+///
+/// #[derive(Element)]
+/// #[element("span")]
+/// #[attrs(id = "count")]
+/// struct Counter {
+///     #[element("b")]
+///     count: usize,
+/// }
+///
+/// let oob = Oob(Counter { count: 3 }, OobSwap::True);
+/// assert_eq!(r#"<span id="count" hx-swap-oob="true"><b>3</b></span>"#, oob.to_htmx());
+///
+/// let oob = Oob(Counter { count: 3 }, OobSwap::target(SwapStyle::BeforeEnd, "#log"));
+/// assert_eq!(
+///     r#"<span id="count" hx-swap-oob="beforeend:#log"><b>3</b></span>"#,
+///     oob.to_htmx()
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Oob<T>(pub T, pub OobSwap);
+
+impl<T: HtmxElement> HtmxElement for Oob<T> {
+    fn try_write_htmx(&self, out: &mut impl Write) -> Result<(), RenderError> {
+        self.0.try_write_htmx_oob(out, &self.1)
+    }
+
+    fn try_write_htmx_fragment(&self, id: &str, out: &mut impl Write) -> Result<bool, RenderError> {
+        self.0.try_write_htmx_fragment(id, out)
+    }
+
+    fn collect_head(&self, head: &mut Head) {
+        self.0.collect_head(head)
+    }
+
+    fn size_hint(&self) -> usize {
+        self.0.size_hint() + " hx-swap-oob=\"\"".len() + 16
+    }
+}
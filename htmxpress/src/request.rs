@@ -0,0 +1,118 @@
+//! Typed access to the headers htmx sends with its requests.
+
+use http::{request::Parts, HeaderMap, Uri};
+
+use crate::headers::*;
+
+/// The htmx request headers of an incoming request.
+///
+/// Missing or malformed headers are treated as absent.
+///
+/// ```
+/// use htmxpress::HxRequest;
+/// use http::{HeaderMap, HeaderValue};
+///
+/// let mut headers = HeaderMap::new();
+/// headers.insert("hx-request", HeaderValue::from_static("true"));
+/// headers.insert("hx-target", HeaderValue::from_static("list"));
+///
+/// let hx = HxRequest::from_headers(&headers);
+///
+/// assert!(hx.is_htmx());
+/// assert!(!hx.is_boosted());
+/// assert_eq!(Some("list"), hx.target());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HxRequest {
+    request: bool,
+    boosted: bool,
+    current_url: Option<Uri>,
+    history_restore_request: bool,
+    prompt: Option<String>,
+    target: Option<String>,
+    trigger: Option<String>,
+    trigger_name: Option<String>,
+}
+
+impl HxRequest {
+    /// Read the htmx headers from the header map.
+    pub fn from_headers(headers: &HeaderMap) -> Self {
+        Self::from_lookup(|name| headers.get(name).map(|value| value.as_bytes()))
+    }
+
+    /// Read the htmx headers using a function returning the value of the header with the given name.
+    ///
+    /// Used by integrations with frameworks whose header types differ from the ones in `http`.
+    pub(crate) fn from_lookup<'a>(get: impl Fn(&str) -> Option<&'a [u8]>) -> Self {
+        let string = |name: http::HeaderName| {
+            get(name.as_str())
+                .and_then(|value| std::str::from_utf8(value).ok())
+                .map(ToString::to_string)
+        };
+        let flag = |name: http::HeaderName| get(name.as_str()) == Some(b"true".as_slice());
+
+        Self {
+            request: flag(HX_REQUEST),
+            boosted: flag(HX_BOOSTED),
+            current_url: string(HX_CURRENT_URL).and_then(|url| url.parse().ok()),
+            history_restore_request: flag(HX_HISTORY_RESTORE_REQUEST),
+            prompt: string(HX_PROMPT),
+            target: string(HX_TARGET),
+            trigger: string(HX_TRIGGER),
+            trigger_name: string(HX_TRIGGER_NAME),
+        }
+    }
+
+    /// `true` if the request was made by htmx, from `HX-Request`.
+    pub fn is_htmx(&self) -> bool {
+        self.request
+    }
+
+    /// `true` if the request was made by an element using `hx-boost`, from `HX-Boosted`.
+    pub fn is_boosted(&self) -> bool {
+        self.boosted
+    }
+
+    /// The current URL of the browser, from `HX-Current-URL`.
+    pub fn current_url(&self) -> Option<&Uri> {
+        self.current_url.as_ref()
+    }
+
+    /// `true` if the request is for history restoration after a miss in the local
+    /// history cache, from `HX-History-Restore-Request`.
+    pub fn is_history_restore_request(&self) -> bool {
+        self.history_restore_request
+    }
+
+    /// The user response to an `hx-prompt`, from `HX-Prompt`.
+    pub fn prompt(&self) -> Option<&str> {
+        self.prompt.as_deref()
+    }
+
+    /// The `id` of the target element if it exists, from `HX-Target`.
+    pub fn target(&self) -> Option<&str> {
+        self.target.as_deref()
+    }
+
+    /// The `id` of the triggered element if it exists, from `HX-Trigger`.
+    pub fn trigger(&self) -> Option<&str> {
+        self.trigger.as_deref()
+    }
+
+    /// The `name` of the triggered element if it exists, from `HX-Trigger-Name`.
+    pub fn trigger_name(&self) -> Option<&str> {
+        self.trigger_name.as_deref()
+    }
+}
+
+impl From<&HeaderMap> for HxRequest {
+    fn from(headers: &HeaderMap) -> Self {
+        Self::from_headers(headers)
+    }
+}
+
+impl From<&Parts> for HxRequest {
+    fn from(parts: &Parts) -> Self {
+        Self::from_headers(&parts.headers)
+    }
+}
@@ -0,0 +1,451 @@
+//! Responses containing rendered elements along with htmx response headers.
+
+use std::fmt::{self, Display};
+
+use http::{header::CONTENT_TYPE, HeaderMap, HeaderName, HeaderValue, StatusCode, Uri};
+
+use crate::{headers::*, json, HtmxElement, HxSwap, OobSwap, RenderError};
+
+/// Wrapper for returning elements from handlers of the supported web frameworks.
+///
+/// The element is rendered into an [HtmxResponse] without any additional headers.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Htmx<T>(pub T);
+
+/// Builder for a response containing a rendered element and htmx response headers.
+///
+/// ```
+/// use htmxpress::{Element, HtmxResponse, HxEvent, SwapStyle};
+///
+/// #[derive(Element)]
+/// #[element("p")]
+/// struct Message {
+///     #[element("span")]
+///     text: &'static str,
+/// }
+///
+/// let response = HtmxResponse::new(&Message { text: "Saved" })
+///     .trigger(HxEvent::new("saved").detail("id", "42"))
+///     .trigger("refreshList")
+///     .retarget("#messages")
+///     .reswap(SwapStyle::BeforeEnd)
+///     .try_into_http()
+///     .unwrap();
+///
+/// assert_eq!(r#"{"saved":{"id":"42"},"refreshList":{}}"#, response.headers()["hx-trigger"]);
+/// assert_eq!("#messages", response.headers()["hx-retarget"]);
+/// assert_eq!("beforeend", response.headers()["hx-reswap"]);
+/// assert_eq!("<p><span>Saved</span></p>", response.body());
+/// ```
+#[derive(Debug)]
+pub struct HtmxResponse {
+    status: StatusCode,
+    headers: HeaderMap,
+    body: String,
+
+    /// Events from `HX-Trigger`, `HX-Trigger-After-Swap` and `HX-Trigger-After-Settle`.
+    /// Kept separate so that multiple calls to the trigger methods get merged into one header.
+    triggers: [Vec<HxEvent>; 3],
+
+    /// The first error that occurred while building the response
+    error: Option<ResponseError>,
+}
+
+impl HtmxResponse {
+    /// Create a response with the HTML of the given element.
+    ///
+    /// Rendering errors are reported by [HtmxResponse::try_into_http].
+    pub fn new(element: &impl HtmxElement) -> Self {
+        match element.try_to_htmx() {
+            Ok(html) => Self::html(html),
+            Err(e) => Self {
+                error: Some(ResponseError::Render(e)),
+                ..Self::empty()
+            },
+        }
+    }
+
+    /// Create a response with already rendered HTML.
+    pub fn html(html: impl Into<String>) -> Self {
+        Self {
+            body: html.into(),
+            ..Self::empty()
+        }
+    }
+
+    /// Create a response without a body, useful for responses consisting only of htmx headers.
+    pub fn empty() -> Self {
+        Self {
+            status: StatusCode::OK,
+            headers: HeaderMap::new(),
+            body: String::new(),
+            triggers: Default::default(),
+            error: None,
+        }
+    }
+
+    /// Append the element to the response as an out of band swap.
+    ///
+    /// Rendering errors are reported by [HtmxResponse::try_into_http].
+    pub fn oob(mut self, element: &impl HtmxElement, swap: impl Into<OobSwap>) -> Self {
+        if let Err(e) = element.try_write_htmx_oob(&mut self.body, &swap.into()) {
+            self.error.get_or_insert(ResponseError::Render(e));
+        }
+        self
+    }
+
+    /// Set the status of the response. Defaults to `200 OK`.
+    pub fn status(mut self, status: StatusCode) -> Self {
+        self.status = status;
+        self
+    }
+
+    /// Set an arbitrary header on the response.
+    pub fn header(mut self, name: HeaderName, value: HeaderValue) -> Self {
+        self.headers.insert(name, value);
+        self
+    }
+
+    /// Trigger a client side event as soon as the response is received, using `HX-Trigger`.
+    pub fn trigger(mut self, event: impl Into<HxEvent>) -> Self {
+        self.triggers[0].push(event.into());
+        self
+    }
+
+    /// Trigger a client side event after the swap step, using `HX-Trigger-After-Swap`.
+    pub fn trigger_after_swap(mut self, event: impl Into<HxEvent>) -> Self {
+        self.triggers[1].push(event.into());
+        self
+    }
+
+    /// Trigger a client side event after the settle step, using `HX-Trigger-After-Settle`.
+    pub fn trigger_after_settle(mut self, event: impl Into<HxEvent>) -> Self {
+        self.triggers[2].push(event.into());
+        self
+    }
+
+    /// Push the URL into the browser history stack, using `HX-Push-Url`.
+    pub fn push_url(self, url: Uri) -> Self {
+        self.set(HX_PUSH_URL, url.to_string())
+    }
+
+    /// Prevent the browser history from being updated, using `HX-Push-Url`.
+    pub fn no_push_url(self) -> Self {
+        self.set(HX_PUSH_URL, "false")
+    }
+
+    /// Replace the current URL in the location bar, using `HX-Replace-Url`.
+    pub fn replace_url(self, url: Uri) -> Self {
+        self.set(HX_REPLACE_URL, url.to_string())
+    }
+
+    /// Prevent the current URL from being replaced, using `HX-Replace-Url`.
+    pub fn no_replace_url(self) -> Self {
+        self.set(HX_REPLACE_URL, "false")
+    }
+
+    /// Redirect to the URL with a full page reload, using `HX-Redirect`.
+    pub fn redirect(self, url: Uri) -> Self {
+        self.set(HX_REDIRECT, url.to_string())
+    }
+
+    /// Do a full refresh of the page, using `HX-Refresh`.
+    pub fn refresh(self) -> Self {
+        self.set(HX_REFRESH, "true")
+    }
+
+    /// Redirect without a full page reload, using `HX-Location`.
+    pub fn location(self, location: impl Into<HxLocation>) -> Self {
+        let location = location.into().header_value();
+        self.set(HX_LOCATION, location)
+    }
+
+    /// Update the target of the content update to the CSS selector, using `HX-Retarget`.
+    pub fn retarget(self, selector: impl Into<String>) -> Self {
+        self.set(HX_RETARGET, selector.into())
+    }
+
+    /// Change how the response will be swapped, using `HX-Reswap`.
+    pub fn reswap(self, swap: impl Into<HxSwap>) -> Self {
+        self.set(HX_RESWAP, swap.into().to_string())
+    }
+
+    /// Choose which part of the response is swapped in with a CSS selector, using `HX-Reselect`.
+    pub fn reselect(self, selector: impl Into<String>) -> Self {
+        self.set(HX_RESELECT, selector.into())
+    }
+
+    /// Build the response, failing if the element could not be rendered
+    /// or a header contained characters not allowed in header values.
+    pub fn try_into_http(mut self) -> Result<http::Response<String>, ResponseError> {
+        let names = [HX_TRIGGER, HX_TRIGGER_AFTER_SWAP, HX_TRIGGER_AFTER_SETTLE];
+        let triggers = std::mem::take(&mut self.triggers);
+
+        for (name, events) in names.into_iter().zip(triggers) {
+            if !events.is_empty() {
+                self = self.set(name, HxEvent::header_value(&events));
+            }
+        }
+
+        if let Some(error) = self.error {
+            return Err(error);
+        }
+
+        let mut response = http::Response::new(self.body);
+        *response.status_mut() = self.status;
+        *response.headers_mut() = self.headers;
+        response
+            .headers_mut()
+            .insert(CONTENT_TYPE, HeaderValue::from_static("text/html"));
+
+        Ok(response)
+    }
+
+    fn set(mut self, name: HeaderName, value: impl Into<String>) -> Self {
+        match HeaderValue::try_from(value.into()) {
+            Ok(value) => {
+                self.headers.insert(name, value);
+            }
+            Err(_) => {
+                self.error.get_or_insert(ResponseError::InvalidHeader(name));
+            }
+        }
+        self
+    }
+}
+
+/// A client side event triggered by the `HX-Trigger*` response headers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HxEvent {
+    name: String,
+    detail: Vec<(String, String)>,
+}
+
+impl HxEvent {
+    /// Create an event without any details.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            detail: vec![],
+        }
+    }
+
+    /// Add a value to the `detail` object of the event.
+    pub fn detail(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.detail.push((key.into(), value.into()));
+        self
+    }
+
+    /// Events without details are written as a comma separated list,
+    /// otherwise all events are written as a JSON object.
+    fn header_value(events: &[HxEvent]) -> String {
+        let mut value = String::new();
+
+        if events.iter().all(|event| event.detail.is_empty()) {
+            for (i, event) in events.iter().enumerate() {
+                if i > 0 {
+                    value.push_str(", ");
+                }
+                value.push_str(&event.name);
+            }
+            return value;
+        }
+
+        value.push('{');
+        for (i, event) in events.iter().enumerate() {
+            if i > 0 {
+                value.push(',');
+            }
+            json::write_str(&mut value, &event.name);
+            value.push(':');
+            json::write_object(
+                &mut value,
+                event.detail.iter().map(|(k, v)| (k.as_str(), v.as_str())),
+            );
+        }
+        value.push('}');
+
+        value
+    }
+}
+
+impl From<&str> for HxEvent {
+    fn from(name: &str) -> Self {
+        Self::new(name)
+    }
+}
+
+impl From<String> for HxEvent {
+    fn from(name: String) -> Self {
+        Self::new(name)
+    }
+}
+
+/// A client side redirect without a full page reload, used with `HX-Location`.
+///
+/// When only the path is set, the header contains just the path,
+/// otherwise the header contains a JSON object with all the options.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HxLocation {
+    path: Uri,
+    source: Option<String>,
+    event: Option<String>,
+    handler: Option<String>,
+    target: Option<String>,
+    swap: Option<HxSwap>,
+    select: Option<String>,
+    values: Vec<(String, String)>,
+    headers: Vec<(String, String)>,
+}
+
+impl HxLocation {
+    /// Create a location pointing to the path.
+    pub fn new(path: Uri) -> Self {
+        Self {
+            path,
+            source: None,
+            event: None,
+            handler: None,
+            target: None,
+            swap: None,
+            select: None,
+            values: vec![],
+            headers: vec![],
+        }
+    }
+
+    /// The source element of the request.
+    pub fn source(mut self, source: impl Into<String>) -> Self {
+        self.source = Some(source.into());
+        self
+    }
+
+    /// An event that "triggered" the request.
+    pub fn event(mut self, event: impl Into<String>) -> Self {
+        self.event = Some(event.into());
+        self
+    }
+
+    /// A callback that will handle the response HTML.
+    pub fn handler(mut self, handler: impl Into<String>) -> Self {
+        self.handler = Some(handler.into());
+        self
+    }
+
+    /// The target to swap the response into.
+    pub fn target(mut self, target: impl Into<String>) -> Self {
+        self.target = Some(target.into());
+        self
+    }
+
+    /// How the response will be swapped in relative to the target.
+    pub fn swap(mut self, swap: impl Into<HxSwap>) -> Self {
+        self.swap = Some(swap.into());
+        self
+    }
+
+    /// Select the content to swap from the response.
+    pub fn select(mut self, select: impl Into<String>) -> Self {
+        self.select = Some(select.into());
+        self
+    }
+
+    /// Add a value to submit with the request.
+    pub fn value(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.values.push((key.into(), value.into()));
+        self
+    }
+
+    /// Add a header to submit with the request.
+    pub fn header(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((key.into(), value.into()));
+        self
+    }
+
+    fn header_value(&self) -> String {
+        let path = self.path.to_string();
+
+        let swap = self.swap.as_ref().map(ToString::to_string);
+
+        let options = [
+            ("source", &self.source),
+            ("event", &self.event),
+            ("handler", &self.handler),
+            ("target", &self.target),
+            ("swap", &swap),
+            ("select", &self.select),
+        ];
+
+        if options.iter().all(|(_, value)| value.is_none())
+            && self.values.is_empty()
+            && self.headers.is_empty()
+        {
+            return path;
+        }
+
+        let mut value = String::from("{");
+        json::write_str(&mut value, "path");
+        value.push(':');
+        json::write_str(&mut value, &path);
+
+        for (key, option) in options {
+            let Some(option) = option else {
+                continue;
+            };
+            value.push(',');
+            json::write_str(&mut value, key);
+            value.push(':');
+            json::write_str(&mut value, option);
+        }
+
+        for (key, entries) in [("values", &self.values), ("headers", &self.headers)] {
+            if entries.is_empty() {
+                continue;
+            }
+            value.push(',');
+            json::write_str(&mut value, key);
+            value.push(':');
+            json::write_object(
+                &mut value,
+                entries.iter().map(|(k, v)| (k.as_str(), v.as_str())),
+            );
+        }
+
+        value.push('}');
+        value
+    }
+}
+
+impl From<Uri> for HxLocation {
+    fn from(path: Uri) -> Self {
+        Self::new(path)
+    }
+}
+
+/// Error returned when building an [HtmxResponse].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ResponseError {
+    /// The element of the response failed to render
+    Render(RenderError),
+
+    /// The value of the header contained characters not allowed in header values
+    InvalidHeader(HeaderName),
+}
+
+impl Display for ResponseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ResponseError::Render(e) => write!(f, "{e}"),
+            ResponseError::InvalidHeader(name) => write!(f, "invalid value for header {name}"),
+        }
+    }
+}
+
+impl std::error::Error for ResponseError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ResponseError::Render(e) => Some(e),
+            ResponseError::InvalidHeader(_) => None,
+        }
+    }
+}
@@ -0,0 +1,245 @@
+use std::{
+    fmt::{self, Display},
+    time::Duration,
+};
+
+/// How content is swapped into the DOM, as used by `hx-swap` and the `HX-Reswap` header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum SwapStyle {
+    /// Replace the inner html of the target element
+    #[default]
+    InnerHtml,
+
+    /// Replace the entire target element with the response
+    OuterHtml,
+
+    /// Replace the text content of the target element, without parsing the response as HTML
+    TextContent,
+
+    /// Insert the response before the target element
+    BeforeBegin,
+
+    /// Insert the response before the first child of the target element
+    AfterBegin,
+
+    /// Insert the response after the last child of the target element
+    BeforeEnd,
+
+    /// Insert the response after the target element
+    AfterEnd,
+
+    /// Deletes the target element regardless of the response
+    Delete,
+
+    /// Does not append content from response
+    None,
+}
+
+impl SwapStyle {
+    /// The value as expected by htmx
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SwapStyle::InnerHtml => "innerHTML",
+            SwapStyle::OuterHtml => "outerHTML",
+            SwapStyle::TextContent => "textContent",
+            SwapStyle::BeforeBegin => "beforebegin",
+            SwapStyle::AfterBegin => "afterbegin",
+            SwapStyle::BeforeEnd => "beforeend",
+            SwapStyle::AfterEnd => "afterend",
+            SwapStyle::Delete => "delete",
+            SwapStyle::None => "none",
+        }
+    }
+}
+
+impl Display for SwapStyle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Where to scroll an element to, used by the `scroll` and `show` modifiers of `hx-swap`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ScrollPosition {
+    Top,
+    Bottom,
+}
+
+impl ScrollPosition {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ScrollPosition::Top => "top",
+            ScrollPosition::Bottom => "bottom",
+        }
+    }
+}
+
+impl Display for ScrollPosition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Scrolling done by the `scroll` and `show` modifiers of `hx-swap`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Scroll {
+    /// Scroll the target element, e.g. `scroll:top`
+    Target(ScrollPosition),
+
+    /// Scroll the element matching the CSS selector, or `window`, e.g. `show:#item:top`
+    Selector(String, ScrollPosition),
+
+    /// Disable scrolling, e.g. `show:none`
+    None,
+}
+
+impl Display for Scroll {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Scroll::Target(position) => write!(f, "{position}"),
+            Scroll::Selector(selector, position) => write!(f, "{selector}:{position}"),
+            Scroll::None => f.write_str("none"),
+        }
+    }
+}
+
+impl From<ScrollPosition> for Scroll {
+    fn from(position: ScrollPosition) -> Self {
+        Self::Target(position)
+    }
+}
+
+/// The value of `hx-swap` and the `HX-Reswap` header, a [SwapStyle] along with its modifiers.
+///
+/// `#[hx_swap(...)]` produces the same values, validated at compile time.
+///
+/// ```
+/// use std::time::Duration;
+/// use htmxpress::{HxSwap, ScrollPosition, SwapStyle};
+///
+/// let swap = HxSwap::new(SwapStyle::OuterHtml)
+///     .swap(Duration::from_secs(1))
+///     .settle(Duration::from_millis(150))
+///     .show(("#item", ScrollPosition::Top))
+///     .transition(true);
+///
+/// assert_eq!("outerHTML swap:1s settle:150ms show:#item:top transition:true", swap.to_string());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct HxSwap {
+    style: SwapStyle,
+    swap: Option<Duration>,
+    settle: Option<Duration>,
+    scroll: Option<Scroll>,
+    show: Option<Scroll>,
+    focus_scroll: Option<bool>,
+    transition: Option<bool>,
+    ignore_title: Option<bool>,
+}
+
+impl HxSwap {
+    pub fn new(style: SwapStyle) -> Self {
+        Self {
+            style,
+            ..Default::default()
+        }
+    }
+
+    /// Delay between the content being removed and new content being inserted, `swap:`.
+    pub fn swap(mut self, delay: Duration) -> Self {
+        self.swap = Some(delay);
+        self
+    }
+
+    /// Delay between the new content being inserted and it being settled, `settle:`.
+    pub fn settle(mut self, delay: Duration) -> Self {
+        self.settle = Some(delay);
+        self
+    }
+
+    /// Scroll the target or another element after swapping, `scroll:`.
+    pub fn scroll(mut self, scroll: impl Into<Scroll>) -> Self {
+        self.scroll = Some(scroll.into());
+        self
+    }
+
+    /// Scroll the target or another element into view after swapping, `show:`.
+    pub fn show(mut self, show: impl Into<Scroll>) -> Self {
+        self.show = Some(show.into());
+        self
+    }
+
+    /// Whether to scroll focused inputs into view, `focus-scroll:`.
+    pub fn focus_scroll(mut self, focus_scroll: bool) -> Self {
+        self.focus_scroll = Some(focus_scroll);
+        self
+    }
+
+    /// Whether to use the View Transitions API for the swap, `transition:`.
+    pub fn transition(mut self, transition: bool) -> Self {
+        self.transition = Some(transition);
+        self
+    }
+
+    /// Whether to ignore any `<title>` in the response, `ignoreTitle:`.
+    pub fn ignore_title(mut self, ignore_title: bool) -> Self {
+        self.ignore_title = Some(ignore_title);
+        self
+    }
+}
+
+impl Display for HxSwap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.style)?;
+
+        if let Some(swap) = self.swap {
+            write!(f, " swap:{}", Timing(swap))?;
+        }
+        if let Some(settle) = self.settle {
+            write!(f, " settle:{}", Timing(settle))?;
+        }
+        if let Some(ref scroll) = self.scroll {
+            write!(f, " scroll:{scroll}")?;
+        }
+        if let Some(ref show) = self.show {
+            write!(f, " show:{show}")?;
+        }
+        if let Some(focus_scroll) = self.focus_scroll {
+            write!(f, " focus-scroll:{focus_scroll}")?;
+        }
+        if let Some(transition) = self.transition {
+            write!(f, " transition:{transition}")?;
+        }
+        if let Some(ignore_title) = self.ignore_title {
+            write!(f, " ignoreTitle:{ignore_title}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl From<SwapStyle> for HxSwap {
+    fn from(style: SwapStyle) -> Self {
+        Self::new(style)
+    }
+}
+
+/// Writes a duration as a htmx timing, i.e. `1s` or `150ms`
+pub(crate) struct Timing(pub Duration);
+
+impl Display for Timing {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let millis = self.0.as_millis();
+        if millis > 0 && millis.is_multiple_of(1000) {
+            write!(f, "{}s", millis / 1000)
+        } else {
+            write!(f, "{millis}ms")
+        }
+    }
+}
+
+impl<S: Into<String>> From<(S, ScrollPosition)> for Scroll {
+    fn from((selector, position): (S, ScrollPosition)) -> Self {
+        Self::Selector(selector.into(), position)
+    }
+}
@@ -0,0 +1,255 @@
+use std::{
+    fmt::{self, Display},
+    time::Duration,
+};
+
+use crate::swap::Timing;
+
+/// How events are queued while a request is in flight, used by the `queue` modifier of `hx-trigger`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TriggerQueue {
+    /// Queue the first event
+    First,
+
+    /// Queue the last event
+    Last,
+
+    /// Queue all events
+    All,
+
+    /// Do not queue new events
+    None,
+}
+
+impl TriggerQueue {
+    /// The value as expected by htmx
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TriggerQueue::First => "first",
+            TriggerQueue::Last => "last",
+            TriggerQueue::All => "all",
+            TriggerQueue::None => "none",
+        }
+    }
+}
+
+impl Display for TriggerQueue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// The value of `hx-trigger`, one or more triggers along with their filters and modifiers.
+///
+/// Modifiers apply to the last trigger added. `#[hx_trigger(...)]` produces the same values,
+/// validated at compile time.
+///
+/// ```
+/// use std::time::Duration;
+/// use htmxpress::{HxTrigger, TriggerQueue};
+///
+/// let trigger = HxTrigger::event("keyup")
+///     .filter("ctrlKey")
+///     .changed()
+///     .delay(Duration::from_millis(500))
+///     .queue(TriggerQueue::Last)
+///     .or(HxTrigger::event("search").from("#form"))
+///     .or(HxTrigger::every(Duration::from_secs(10)));
+///
+/// assert_eq!(
+///     "keyup[ctrlKey] changed delay:500ms queue:last, search from:#form, every 10s",
+///     trigger.to_string()
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct HxTrigger {
+    triggers: Vec<Trigger>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Trigger {
+    event: Event,
+    filter: Option<String>,
+    once: bool,
+    changed: bool,
+    delay: Option<Duration>,
+    throttle: Option<Duration>,
+    from: Option<String>,
+    target: Option<String>,
+    consume: bool,
+    queue: Option<TriggerQueue>,
+    root: Option<String>,
+    threshold: Option<f32>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Event {
+    Named(String),
+    Every(Duration),
+}
+
+impl HxTrigger {
+    /// Trigger on the event with the given name, e.g. `click` or `htmx:afterSwap`.
+    pub fn event(name: impl Into<String>) -> Self {
+        Self::with(Event::Named(name.into()))
+    }
+
+    /// Trigger periodically, `every`.
+    pub fn every(interval: Duration) -> Self {
+        Self::with(Event::Every(interval))
+    }
+
+    fn with(event: Event) -> Self {
+        Self {
+            triggers: vec![Trigger {
+                event,
+                filter: None,
+                once: false,
+                changed: false,
+                delay: None,
+                throttle: None,
+                from: None,
+                target: None,
+                consume: false,
+                queue: None,
+                root: None,
+                threshold: None,
+            }],
+        }
+    }
+
+    /// Append the triggers of `other`, any of them will trigger the request.
+    pub fn or(mut self, other: HxTrigger) -> Self {
+        self.triggers.extend(other.triggers);
+        self
+    }
+
+    /// Only trigger if the javascript expression is true, e.g. `ctrlKey`.
+    pub fn filter(mut self, filter: impl Into<String>) -> Self {
+        self.last().filter = Some(filter.into());
+        self
+    }
+
+    /// Trigger only once, `once`.
+    pub fn once(mut self) -> Self {
+        self.last().once = true;
+        self
+    }
+
+    /// Trigger only if the value of the element changed, `changed`.
+    pub fn changed(mut self) -> Self {
+        self.last().changed = true;
+        self
+    }
+
+    /// Wait before triggering, resetting the delay if the event is seen again, `delay:`.
+    pub fn delay(mut self, delay: Duration) -> Self {
+        self.last().delay = Some(delay);
+        self
+    }
+
+    /// Ignore events for the given time after triggering, `throttle:`.
+    pub fn throttle(mut self, throttle: Duration) -> Self {
+        self.last().throttle = Some(throttle);
+        self
+    }
+
+    /// Listen for the event on another element, e.g. `document` or `closest form`, `from:`.
+    pub fn from(mut self, from: impl Into<String>) -> Self {
+        self.last().from = Some(from.into());
+        self
+    }
+
+    /// Only trigger if the event target matches the CSS selector, `target:`.
+    pub fn target(mut self, target: impl Into<String>) -> Self {
+        self.last().target = Some(target.into());
+        self
+    }
+
+    /// Stop the event from triggering requests on parent elements, `consume`.
+    pub fn consume(mut self) -> Self {
+        self.last().consume = true;
+        self
+    }
+
+    /// How to queue events while a request is in flight, `queue:`.
+    pub fn queue(mut self, queue: TriggerQueue) -> Self {
+        self.last().queue = Some(queue);
+        self
+    }
+
+    /// The root element of an `intersect` trigger, `root:`.
+    pub fn root(mut self, root: impl Into<String>) -> Self {
+        self.last().root = Some(root.into());
+        self
+    }
+
+    /// How much of the element has to be visible for an `intersect` trigger, `threshold:`.
+    pub fn threshold(mut self, threshold: f32) -> Self {
+        self.last().threshold = Some(threshold);
+        self
+    }
+
+    fn last(&mut self) -> &mut Trigger {
+        self.triggers
+            .last_mut()
+            .expect("a trigger always has at least one event")
+    }
+}
+
+impl Display for HxTrigger {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, trigger) in self.triggers.iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "{trigger}")?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for Trigger {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.event {
+            Event::Named(ref name) => f.write_str(name)?,
+            Event::Every(interval) => write!(f, "every {}", Timing(interval))?,
+        }
+
+        if let Some(ref filter) = self.filter {
+            write!(f, "[{filter}]")?;
+        }
+        if self.once {
+            f.write_str(" once")?;
+        }
+        if self.changed {
+            f.write_str(" changed")?;
+        }
+        if let Some(delay) = self.delay {
+            write!(f, " delay:{}", Timing(delay))?;
+        }
+        if let Some(throttle) = self.throttle {
+            write!(f, " throttle:{}", Timing(throttle))?;
+        }
+        if let Some(ref from) = self.from {
+            write!(f, " from:{from}")?;
+        }
+        if let Some(ref target) = self.target {
+            write!(f, " target:{target}")?;
+        }
+        if self.consume {
+            f.write_str(" consume")?;
+        }
+        if let Some(queue) = self.queue {
+            write!(f, " queue:{queue}")?;
+        }
+        if let Some(ref root) = self.root {
+            write!(f, " root:{root}")?;
+        }
+        if let Some(threshold) = self.threshold {
+            write!(f, " threshold:{threshold}")?;
+        }
+
+        Ok(())
+    }
+}
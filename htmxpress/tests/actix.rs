@@ -0,0 +1,113 @@
+use actix_web::{
+    body::to_bytes,
+    http::StatusCode,
+    test::{self, TestRequest},
+    web, App, HttpResponse,
+};
+use htmxpress::{Element, Htmx, HtmxResponse, HxEvent, HxRequest};
+
+#[derive(Element)]
+#[element("li")]
+#[responder]
+struct Item {
+    #[element("span")]
+    name: &'static str,
+}
+
+async fn body(response: actix_web::dev::ServiceResponse) -> String {
+    let body = to_bytes(response.into_body()).await.unwrap();
+    String::from_utf8(body.to_vec()).unwrap()
+}
+
+#[actix_web::test]
+async fn derived_responder() {
+    let app =
+        test::init_service(App::new().route("/", web::get().to(|| async { Item { name: "Foo" } })))
+            .await;
+
+    let response = test::call_service(&app, TestRequest::get().uri("/").to_request()).await;
+
+    assert_eq!(StatusCode::OK, response.status());
+    assert_eq!("text/html", response.headers().get("content-type").unwrap());
+    assert_eq!("<li><span>Foo</span></li>", body(response).await);
+}
+
+#[actix_web::test]
+async fn wrapper() {
+    let app = test::init_service(
+        App::new().route("/", web::get().to(|| async { Htmx(Item { name: "Bar" }) })),
+    )
+    .await;
+
+    let response = test::call_service(&app, TestRequest::get().uri("/").to_request()).await;
+
+    assert_eq!("<li><span>Bar</span></li>", body(response).await);
+}
+
+#[actix_web::test]
+async fn response_builder() {
+    let app = test::init_service(App::new().route(
+        "/",
+        web::get().to(|| async {
+            HtmxResponse::new(&Item { name: "Baz" })
+                .status(http::StatusCode::ACCEPTED)
+                .trigger(HxEvent::new("first"))
+                .trigger("second")
+        }),
+    ))
+    .await;
+
+    let response = test::call_service(&app, TestRequest::get().uri("/").to_request()).await;
+
+    assert_eq!(StatusCode::ACCEPTED, response.status());
+    assert_eq!(
+        "first, second",
+        response.headers().get("hx-trigger").unwrap()
+    );
+    assert_eq!("<li><span>Baz</span></li>", body(response).await);
+}
+
+#[actix_web::test]
+async fn invalid_response() {
+    let app = test::init_service(App::new().route(
+        "/",
+        web::get().to(|| async { HtmxResponse::empty().retarget("#list\n") }),
+    ))
+    .await;
+
+    let response = test::call_service(&app, TestRequest::get().uri("/").to_request()).await;
+
+    assert_eq!(StatusCode::INTERNAL_SERVER_ERROR, response.status());
+    assert_eq!(
+        Some("invalid value for header hx-retarget".to_string()),
+        response.response().error().map(ToString::to_string)
+    );
+    assert!(body(response).await.is_empty());
+}
+
+#[actix_web::test]
+async fn extractor() {
+    let app = test::init_service(App::new().route(
+        "/",
+        web::get().to(|hx: HxRequest| async move {
+            HttpResponse::Ok().body(format!(
+                "{} {}",
+                hx.is_htmx(),
+                hx.target().unwrap_or("none")
+            ))
+        }),
+    ))
+    .await;
+
+    let response = test::call_service(&app, TestRequest::get().uri("/").to_request()).await;
+    assert_eq!("false none", body(response).await);
+
+    let request = TestRequest::get()
+        .uri("/")
+        .insert_header(("HX-Request", "true"))
+        .insert_header(("HX-Target", "list"))
+        .to_request();
+
+    let response = test::call_service(&app, request).await;
+    assert_eq!("true list", body(response).await);
+}
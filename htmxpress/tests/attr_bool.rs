@@ -0,0 +1,151 @@
+use htmxpress::{Element, HtmxElement};
+
+const ALWAYS: bool = true;
+
+fn is_empty(items: &[&str]) -> bool {
+    items.is_empty()
+}
+
+#[derive(Element)]
+#[element("fieldset")]
+#[attr_bool("disabled", locked)]
+#[attr_bool("hidden", is_empty(&items))]
+struct Settings {
+    locked: bool,
+    items: Vec<&'static str>,
+
+    #[element("input")]
+    #[attr("type" = "checkbox")]
+    #[attr_bool("checked", enabled && !locked)]
+    #[attr_bool("required", ALWAYS)]
+    enabled: bool,
+
+    #[element("button")]
+    #[attr_bool("disabled", count.map_or(true, |c| c == 0))]
+    count: Option<usize>,
+}
+
+#[test]
+fn written_when_true() {
+    let settings = Settings {
+        locked: false,
+        items: vec![],
+        enabled: true,
+        count: Some(0),
+    };
+
+    assert_eq!(
+        concat!(
+            r#"<fieldset hidden>"#,
+            r#"<input type="checkbox" checked required>"#,
+            r#"<button disabled>0</button>"#,
+            r#"</fieldset>"#
+        ),
+        settings.to_htmx()
+    );
+
+    let settings = Settings {
+        locked: true,
+        items: vec!["a"],
+        enabled: true,
+        count: Some(2),
+    };
+
+    assert_eq!(
+        concat!(
+            r#"<fieldset disabled>"#,
+            r#"<input type="checkbox" required>"#,
+            r#"<button>2</button>"#,
+            r#"</fieldset>"#
+        ),
+        settings.to_htmx()
+    );
+}
+
+#[derive(Element)]
+#[element("option")]
+#[attr_bool("selected", 1)]
+struct Option_(#[element("span")] &'static str, bool);
+
+#[derive(Element)]
+enum Toggle {
+    #[element("button")]
+    #[attr_bool("disabled", !active)]
+    Button {
+        #[element("b")]
+        label: &'static str,
+        active: bool,
+    },
+}
+
+#[test]
+fn tuples_and_enums() {
+    assert_eq!(
+        r#"<option selected><span>A</span></option>"#,
+        Option_("A", true).to_htmx()
+    );
+    assert_eq!(
+        r#"<option><span>B</span></option>"#,
+        Option_("B", false).to_htmx()
+    );
+
+    let toggle = Toggle::Button {
+        label: "Go",
+        active: false,
+    };
+    assert_eq!(r#"<button disabled><b>Go</b></button>"#, toggle.to_htmx());
+}
+
+#[derive(PartialEq)]
+enum State {
+    Open,
+    Locked,
+}
+
+#[derive(Element)]
+#[element("form")]
+#[attr_bool("disabled", matches!(state, State::Locked))]
+#[attr_bool("hidden", tags.iter().any(|tag| *tag == hidden_tag))]
+#[attr_bool("inert", { let state = &State::Open; *state == self.state })]
+struct Editor {
+    state: State,
+    tags: Vec<&'static str>,
+    hidden_tag: &'static str,
+}
+
+#[test]
+fn macros_and_closures() {
+    let editor = Editor {
+        state: State::Locked,
+        tags: vec!["draft"],
+        hidden_tag: "draft",
+    };
+    assert_eq!(r#"<form disabled hidden></form>"#, editor.to_htmx());
+
+    let editor = Editor {
+        state: State::Open,
+        tags: vec!["draft"],
+        hidden_tag: "archived",
+    };
+    assert_eq!(r#"<form inert></form>"#, editor.to_htmx());
+}
+
+#[derive(Element)]
+enum Link {
+    #[element("a")]
+    #[attr_bool("hidden", self.0.is_empty())]
+    #[attr_bool("download", matches!(self.1, Some(name) if !name.is_empty()))]
+    File(#[element("span")] &'static str, Option<&'static str>),
+}
+
+#[test]
+fn self_in_enums() {
+    assert_eq!(
+        r#"<a download><span>report.pdf</span></a>"#,
+        Link::File("report.pdf", Some("report")).to_htmx()
+    );
+    assert_eq!(
+        r#"<a hidden><span></span></a>"#,
+        Link::File("", None).to_htmx()
+    );
+}
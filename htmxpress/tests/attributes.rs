@@ -0,0 +1,75 @@
+use htmxpress::{Element, HtmxElement};
+
+#[derive(Element)]
+#[element("button")]
+#[attr_bool("disabled", locked)]
+#[hx_post("/items/{}", id)]
+#[attrs(id = "save", name = "save")]
+#[hx_swap(outerHTML)]
+#[hx("target" = "#items")]
+struct Save {
+    id: usize,
+    locked: bool,
+
+    #[element("span")]
+    label: &'static str,
+}
+
+#[test]
+fn declaration_order() {
+    let save = Save {
+        id: 1,
+        locked: true,
+        label: "Save",
+    };
+
+    assert_eq!(
+        r##"<button disabled hx-post="/items/1" id="save" name="save" hx-swap="outerHTML" hx-target="#items"><span>Save</span></button>"##,
+        save.to_htmx()
+    );
+}
+
+#[derive(Element)]
+#[element("div")]
+#[class("card", "selected" if selected)]
+#[attrs(style = "color: red;", class = "shadow")]
+#[attr("class" = "card-{}", kind)]
+#[attr("style" = "width: {}px;", width)]
+#[attrs(style = "margin: 0")]
+struct Card {
+    kind: &'static str,
+    width: usize,
+    selected: bool,
+
+    #[element("p")]
+    #[attrs(class = "title")]
+    #[attr("class" = "title-{}", kind)]
+    title: &'static str,
+}
+
+#[test]
+fn merges_class_and_style() {
+    let card = Card {
+        kind: "wide",
+        width: 300,
+        selected: true,
+        title: "Title",
+    };
+
+    assert_eq!(
+        r#"<div class="card shadow card-wide selected" style="color: red; width: 300px; margin: 0"><p class="title title-wide">Title</p></div>"#,
+        card.to_htmx()
+    );
+
+    let card = Card {
+        kind: "narrow",
+        width: 100,
+        selected: false,
+        title: "Title",
+    };
+
+    assert_eq!(
+        r#"<div class="card shadow card-narrow" style="color: red; width: 100px; margin: 0"><p class="title title-narrow">Title</p></div>"#,
+        card.to_htmx()
+    );
+}
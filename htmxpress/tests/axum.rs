@@ -0,0 +1,127 @@
+use axum::{
+    body::Body,
+    response::{IntoResponse, Response},
+    routing::get,
+    Router,
+};
+use htmxpress::{Element, Htmx, HtmxResponse, HxRequest, ResponseError, SwapStyle};
+use http::{Request, StatusCode};
+use http_body_util::BodyExt;
+use tower::ServiceExt;
+
+#[derive(Element)]
+#[element("li")]
+#[into_response]
+struct Item {
+    #[element("span")]
+    name: &'static str,
+}
+
+async fn send(router: Router, request: Request<Body>) -> (http::response::Parts, String) {
+    let response = router.oneshot(request).await.unwrap();
+    let (parts, body) = response.into_parts();
+    let body = body.collect().await.unwrap().to_bytes();
+    (parts, String::from_utf8(body.to_vec()).unwrap())
+}
+
+fn request(uri: &str) -> Request<Body> {
+    Request::builder().uri(uri).body(Body::empty()).unwrap()
+}
+
+#[tokio::test]
+async fn derived_into_response() {
+    let router = Router::new().route("/", get(|| async { Item { name: "Foo" } }));
+
+    let (parts, body) = send(router, request("/")).await;
+
+    assert_eq!(StatusCode::OK, parts.status);
+    assert_eq!("text/html", parts.headers["content-type"]);
+    assert_eq!("<li><span>Foo</span></li>", body);
+}
+
+#[tokio::test]
+async fn wrapper() {
+    let router = Router::new().route("/", get(|| async { Htmx(Item { name: "Bar" }) }));
+
+    let (_, body) = send(router, request("/")).await;
+
+    assert_eq!("<li><span>Bar</span></li>", body);
+}
+
+#[tokio::test]
+async fn response_builder() {
+    let router = Router::new().route(
+        "/",
+        get(|| async {
+            HtmxResponse::new(&Item { name: "Baz" })
+                .status(StatusCode::CREATED)
+                .reswap(SwapStyle::BeforeEnd)
+        }),
+    );
+
+    let (parts, body) = send(router, request("/")).await;
+
+    assert_eq!(StatusCode::CREATED, parts.status);
+    assert_eq!("beforeend", parts.headers["hx-reswap"]);
+    assert_eq!("<li><span>Baz</span></li>", body);
+}
+
+#[tokio::test]
+async fn explicit_into_response() {
+    // Resolves to axum's conversion rather than a method of the response itself
+    let router = Router::new().route(
+        "/",
+        get(|| async {
+            let response: Response = HtmxResponse::empty()
+                .status(StatusCode::NO_CONTENT)
+                .into_response();
+            response
+        }),
+    );
+
+    let (parts, _) = send(router, request("/")).await;
+
+    assert_eq!(StatusCode::NO_CONTENT, parts.status);
+}
+
+#[tokio::test]
+async fn invalid_response() {
+    let router = Router::new().route(
+        "/",
+        get(|| async { HtmxResponse::empty().retarget("#list\n") }),
+    );
+
+    let (parts, body) = send(router, request("/")).await;
+
+    assert_eq!(StatusCode::INTERNAL_SERVER_ERROR, parts.status);
+    assert!(body.is_empty());
+    assert_eq!(
+        Some(&ResponseError::InvalidHeader(
+            http::HeaderName::from_static("hx-retarget")
+        )),
+        parts.extensions.get::<ResponseError>()
+    );
+}
+
+#[tokio::test]
+async fn extractor() {
+    let router = Router::new().route(
+        "/",
+        get(|hx: HxRequest| async move {
+            format!("{} {}", hx.is_htmx(), hx.target().unwrap_or("none"))
+        }),
+    );
+
+    let (_, body) = send(router.clone(), request("/")).await;
+    assert_eq!("false none", body);
+
+    let request = Request::builder()
+        .uri("/")
+        .header("HX-Request", "true")
+        .header("HX-Target", "list")
+        .body(Body::empty())
+        .unwrap();
+
+    let (_, body) = send(router, request).await;
+    assert_eq!("true list", body);
+}
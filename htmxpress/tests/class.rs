@@ -0,0 +1,72 @@
+use htmxpress::{Element, HtmxElement};
+
+#[derive(Element)]
+#[element("li")]
+#[attrs(id = "tab", class = "tab")]
+#[class("active" if selected, "muted" if !enabled)]
+struct Tab {
+    selected: bool,
+    enabled: bool,
+
+    #[element("a")]
+    #[class("badge" if count > 0, "badge-many" if count > 9)]
+    count: usize,
+}
+
+#[test]
+fn merged_with_static_class() {
+    let tab = Tab {
+        selected: true,
+        enabled: false,
+        count: 12,
+    };
+
+    assert_eq!(
+        r#"<li id="tab" class="tab active muted"><a class="badge badge-many">12</a></li>"#,
+        tab.to_htmx()
+    );
+
+    let tab = Tab {
+        selected: false,
+        enabled: true,
+        count: 0,
+    };
+
+    assert_eq!(r#"<li id="tab" class="tab"><a>0</a></li>"#, tab.to_htmx());
+}
+
+#[derive(Element)]
+#[element("tr")]
+#[class("row", "selected" if 1)]
+struct Row(#[element("td")] &'static str, bool);
+
+#[derive(Element)]
+enum Cell {
+    #[element("td")]
+    #[class("warning" if value < 0, "<escaped>" if value == 0)]
+    Number {
+        #[element("span")]
+        value: i32,
+    },
+}
+
+#[test]
+fn tuples_and_enums() {
+    assert_eq!(
+        r#"<tr class="row selected"><td>A</td></tr>"#,
+        Row("A", true).to_htmx()
+    );
+    assert_eq!(
+        r#"<tr class="row"><td>B</td></tr>"#,
+        Row("B", false).to_htmx()
+    );
+
+    assert_eq!(
+        r#"<td class="warning"><span>-1</span></td>"#,
+        Cell::Number { value: -1 }.to_htmx()
+    );
+    assert_eq!(
+        r#"<td class="&lt;escaped&gt;"><span>0</span></td>"#,
+        Cell::Number { value: 0 }.to_htmx()
+    );
+}
@@ -0,0 +1,62 @@
+use htmxpress::{Element, HtmxElement};
+
+#[derive(Element)]
+#[element("section")]
+#[attrs(id = "state")]
+enum State {
+    #[element("p")]
+    #[attrs(class = "loading")]
+    #[before("Loading...")]
+    Loading,
+
+    #[element("p")]
+    #[attrs(class = "error")]
+    Error(#[element("span")] String),
+
+    #[element("div")]
+    #[hx_get("/data/{}", id)]
+    Loaded {
+        id: usize,
+
+        #[element("h1")]
+        #[format("{} #{}", id)]
+        title: String,
+
+        #[nest]
+        data: Data,
+    },
+}
+
+#[derive(Element)]
+#[element("ul")]
+struct Data {
+    #[list]
+    #[element("li")]
+    items: Vec<&'static str>,
+}
+
+#[test]
+fn renders_unit_variant() {
+    let html = r#"<section id="state"><p class="loading">Loading...</p></section>"#;
+    assert_eq!(html, State::Loading.to_htmx());
+}
+
+#[test]
+fn renders_tuple_variant() {
+    let html = r#"<section id="state"><p class="error"><span>&lt;oops&gt;</span></p></section>"#;
+    assert_eq!(html, State::Error("<oops>".to_string()).to_htmx());
+}
+
+#[test]
+fn renders_named_variant() {
+    let state = State::Loaded {
+        id: 1,
+        title: "Data".to_string(),
+        data: Data {
+            items: vec!["foo", "bar"],
+        },
+    };
+
+    let html = r#"<section id="state"><div hx-get="/data/1"><h1>Data #1</h1><ul><li>foo</li><li>bar</li></ul></div></section>"#;
+    assert_eq!(html, state.to_htmx());
+}
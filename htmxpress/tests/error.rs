@@ -0,0 +1,60 @@
+use std::fmt::{self, Display};
+
+use htmxpress::{Element, HtmxElement, RenderLocation};
+
+struct Faulty;
+
+impl Display for Faulty {
+    fn fmt(&self, _: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Err(fmt::Error)
+    }
+}
+
+#[derive(Element)]
+#[element("div")]
+struct Parent {
+    #[element("p")]
+    ok: &'static str,
+
+    #[nest]
+    child: Child,
+}
+
+#[derive(Element)]
+#[element("section")]
+struct Child {
+    #[element("p")]
+    faulty: Faulty,
+}
+
+#[test]
+fn reports_failing_field() {
+    let parent = Parent {
+        ok: "ok",
+        child: Child { faulty: Faulty },
+    };
+
+    let err = parent.try_to_htmx().unwrap_err();
+
+    assert_eq!(
+        err.path(),
+        &[
+            RenderLocation {
+                element: "Parent",
+                field: Some("child")
+            },
+            RenderLocation {
+                element: "Child",
+                field: Some("faulty")
+            }
+        ]
+    );
+    assert_eq!(
+        "failed to render Parent.child > Child.faulty",
+        err.to_string()
+    );
+
+    let mut html = String::new();
+    assert!(parent.write_htmx(&mut html).is_err());
+    assert_eq!(r#"<div><p>ok</p><section><p>"#, html);
+}
@@ -0,0 +1,140 @@
+use std::fmt::{self, Display};
+
+use htmxpress::{Element, HtmxElement, Markup, PreEscaped};
+
+#[derive(Element)]
+#[element("div")]
+#[attr("title" = "{}", title)]
+#[attrs(data_quote = "\"quoted\"")]
+struct Test {
+    title: String,
+
+    #[element("p")]
+    #[format("<b>{}</b>")]
+    content: String,
+
+    #[element("p")]
+    #[raw]
+    raw: String,
+
+    #[element("p")]
+    markup: Markup,
+
+    #[element("p")]
+    maybe_markup: Option<PreEscaped<&'static str>>,
+
+    #[element("li")]
+    #[list]
+    items: Vec<&'static str>,
+}
+
+#[test]
+fn escapes_content_and_attributes() {
+    let test = Test {
+        title: r#"" onclick="alert('xss')"#.to_string(),
+        content: "<script>alert(1)</script>".to_string(),
+        raw: "<i>raw</i>".to_string(),
+        markup: PreEscaped("<i>markup</i>".to_string()),
+        maybe_markup: Some(PreEscaped("<i>maybe</i>")),
+        items: vec!["a & b", "'c'"],
+    };
+
+    let html = r#"<div title="&quot; onclick=&quot;alert(&#39;xss&#39;)" data_quote="&quot;quoted&quot;"><p><b>&lt;script&gt;alert(1)&lt;/script&gt;</b></p><p><i>raw</i></p><p><i>markup</i></p><p><i>maybe</i></p><li>a &amp; b</li><li>'c'</li></div>"#;
+
+    assert_eq!(html, test.to_htmx());
+}
+
+struct Wrap<T>(T);
+
+impl<T: Display> Display for Wrap<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+#[derive(Element)]
+#[element("div")]
+struct Wrapped {
+    #[element("p")]
+    wrapped: Wrap<PreEscaped<&'static str>>,
+
+    #[element("p")]
+    #[list]
+    markups: Vec<PreEscaped<&'static str>>,
+
+    #[element("p")]
+    markup_ref: &'static PreEscaped<&'static str>,
+}
+
+#[test]
+fn escapes_generic_wrappers_of_pre_escaped() {
+    let wrapped = Wrapped {
+        wrapped: Wrap(PreEscaped("<script>")),
+        markups: vec![PreEscaped("<i>a</i>")],
+        markup_ref: &PreEscaped("<b>b</b>"),
+    };
+
+    assert_eq!(
+        "<div><p>&lt;script&gt;</p><p><i>a</i></p><p><b>b</b></p></div>",
+        wrapped.to_htmx()
+    );
+}
+
+#[derive(Element)]
+#[element("li")]
+struct Comment {
+    #[element("div")]
+    #[format("{} by {} ({})", author, badge)]
+    body: Markup,
+
+    author: String,
+
+    #[raw]
+    badge: String,
+}
+
+#[test]
+fn escapes_format_args_by_their_own_type() {
+    let comment = Comment {
+        body: PreEscaped("<em>hi</em>".to_string()),
+        author: "<script>alert(1)</script>".to_string(),
+        badge: "<b>mod</b>".to_string(),
+    };
+
+    assert_eq!(
+        "<li><div><em>hi</em> by &lt;script&gt;alert(1)&lt;/script&gt; (<b>mod</b>)</div></li>",
+        comment.to_htmx()
+    );
+}
+
+mod other {
+    /// Unrelated to `htmxpress::Markup`
+    pub type Markup = String;
+}
+
+#[derive(Element)]
+#[element("div")]
+struct Paths {
+    #[element("p")]
+    other: other::Markup,
+
+    #[element("p")]
+    markup: htmxpress::Markup,
+
+    #[element("p")]
+    pre_escaped: htmxpress::escape::PreEscaped<&'static str>,
+}
+
+#[test]
+fn escapes_other_types_named_like_pre_escaped() {
+    let paths = Paths {
+        other: "<script>".to_string(),
+        markup: PreEscaped("<i>a</i>".to_string()),
+        pre_escaped: PreEscaped("<b>b</b>"),
+    };
+
+    assert_eq!(
+        "<div><p>&lt;script&gt;</p><p><i>a</i></p><p><b>b</b></p></div>",
+        paths.to_htmx()
+    );
+}
@@ -0,0 +1,242 @@
+use htmxpress::{Element, Form, HtmxElement, HtmxResponse, OobSwap, Select, ValidationErrors};
+use http::StatusCode;
+use serde::Deserialize;
+
+#[derive(Debug, PartialEq, Deserialize, Select)]
+#[serde(rename_all = "kebab-case")]
+enum Status {
+    Open,
+    InProgress,
+    #[serde(rename = "done")]
+    #[label("Done!")]
+    Closed,
+}
+
+#[derive(Debug, PartialEq, Deserialize, Select)]
+enum Priority {
+    Low,
+    High,
+}
+
+#[derive(Debug, PartialEq, Deserialize, Element)]
+#[form]
+#[hx_post("/issues")]
+#[serde(rename_all = "camelCase")]
+#[after(r#"<button type="submit">Save</button>"#)]
+struct Issue {
+    title: String,
+    #[label("Estimate (hours)")]
+    estimated_hours: f32,
+    #[serde(default, deserialize_with = "htmxpress::empty_as_none")]
+    story_points: Option<u8>,
+    #[serde(default)]
+    urgent: bool,
+    status: Status,
+    #[serde(default, deserialize_with = "htmxpress::empty_as_none")]
+    priority: Option<Priority>,
+    #[serde(rename = "mail")]
+    #[input("email")]
+    reporter: String,
+    #[input("hidden")]
+    id: usize,
+    #[serde(skip)]
+    internal: usize,
+}
+
+fn issue() -> Issue {
+    Issue {
+        title: r#"Fix "escaping""#.to_string(),
+        estimated_hours: 1.5,
+        story_points: None,
+        urgent: true,
+        status: Status::InProgress,
+        priority: None,
+        reporter: "me@example.com".to_string(),
+        id: 7,
+        internal: 0,
+    }
+}
+
+#[test]
+fn renders_controls() {
+    let html = concat!(
+        r#"<form hx-post="/issues">"#,
+        r#"<label>Title<input type="text" name="title" value="Fix &quot;escaping&quot;" required></label>"#,
+        r#"<label>Estimate (hours)<input type="number" name="estimatedHours" step="any" value="1.5" required></label>"#,
+        r#"<label>Story points<input type="number" name="storyPoints"></label>"#,
+        r#"<label>Urgent<input type="checkbox" name="urgent" value="true" checked></label>"#,
+        r#"<label>Status<select name="status" required>"#,
+        r#"<option value="open">Open</option>"#,
+        r#"<option value="in-progress" selected>In progress</option>"#,
+        r#"<option value="done">Done!</option>"#,
+        r#"</select></label>"#,
+        r#"<label>Priority<select name="priority">"#,
+        r#"<option value=""></option>"#,
+        r#"<option value="Low">Low</option>"#,
+        r#"<option value="High">High</option>"#,
+        r#"</select></label>"#,
+        r#"<label>Reporter<input type="email" name="mail" value="me@example.com" required></label>"#,
+        r#"<input type="hidden" name="id" value="7">"#,
+        r#"<button type="submit">Save</button>"#,
+        r#"</form>"#
+    );
+
+    assert_eq!(html, issue().to_htmx());
+}
+
+#[test]
+fn submission_round_trips() {
+    // Everything a browser submits for the rendered form, including the empty controls
+    let submitted = concat!(
+        "title=Fix+%22escaping%22&estimatedHours=1.5&storyPoints=&urgent=true",
+        "&status=in-progress&priority=&mail=me%40example.com&id=7"
+    );
+
+    assert_eq!(
+        issue(),
+        serde_urlencoded::from_str::<Issue>(submitted).unwrap()
+    );
+
+    let issue = Issue {
+        story_points: Some(3),
+        urgent: false,
+        status: Status::Closed,
+        priority: Some(Priority::High),
+        ..issue()
+    };
+
+    // Unchecked checkboxes are left out
+    let submitted = concat!(
+        "title=Fix+%22escaping%22&estimatedHours=1.5&storyPoints=3",
+        "&status=done&priority=High&mail=me%40example.com&id=7"
+    );
+
+    assert_eq!(
+        issue,
+        serde_urlencoded::from_str::<Issue>(submitted).unwrap()
+    );
+}
+
+// Not deserialized, but still checked as if it were
+#[derive(Element)]
+#[form]
+#[element("fieldset")]
+#[serde(default)]
+struct Filter {
+    #[serde(deserialize_with = "htmxpress::empty_as_none")]
+    query: Option<String>,
+    closed: bool,
+
+    #[element("button")]
+    #[attr("type" = "submit")]
+    submit: &'static str,
+}
+
+#[test]
+fn custom_element() {
+    let filter = Filter {
+        query: Some("bug".to_string()),
+        closed: false,
+        submit: "Search",
+    };
+
+    let html = concat!(
+        r#"<fieldset>"#,
+        r#"<label>Query<input type="text" name="query" value="bug"></label>"#,
+        r#"<label>Closed<input type="checkbox" name="closed" value="true"></label>"#,
+        r#"<button type="submit">Search</button>"#,
+        r#"</fieldset>"#
+    );
+
+    assert_eq!(html, filter.to_htmx());
+}
+
+#[derive(Debug, Deserialize, Element)]
+#[form]
+#[hx_post("/signup")]
+#[attrs(id = "signup")]
+struct Signup {
+    #[input("email")]
+    email: String,
+    #[serde(default, deserialize_with = "htmxpress::empty_as_none")]
+    age: Option<u8>,
+    #[serde(default)]
+    terms: bool,
+    #[serde(default, deserialize_with = "htmxpress::empty_as_none")]
+    role: Option<Priority>,
+}
+
+#[test]
+fn validation_errors() {
+    let signup = serde_urlencoded::from_str::<Signup>("email=foo%40&age=12&role=").unwrap();
+
+    let errors = ValidationErrors::new()
+        .with("email", "Invalid <email>")
+        .with("terms", "Required")
+        .with("role", "Pick a role");
+
+    let html = concat!(
+        r#"<form hx-post="/signup" id="signup">"#,
+        r#"<label>Email<input type="email" name="email" value="foo@" required aria-invalid="true" aria-describedby="email-error"></label>"#,
+        r#"<span id="email-error" class="error">Invalid &lt;email&gt;</span>"#,
+        r#"<label>Age<input type="number" name="age" value="12"></label>"#,
+        r#"<label>Terms<input type="checkbox" name="terms" value="true" aria-invalid="true" aria-describedby="terms-error"></label>"#,
+        r#"<span id="terms-error" class="error">Required</span>"#,
+        r#"<label>Role<select name="role" aria-invalid="true" aria-describedby="role-error">"#,
+        r#"<option value=""></option><option value="Low">Low</option><option value="High">High</option>"#,
+        r#"</select></label>"#,
+        r#"<span id="role-error" class="error">Pick a role</span>"#,
+        r#"</form>"#
+    );
+
+    assert_eq!(html, signup.with_errors(&errors).to_htmx());
+
+    let response = HtmxResponse::new(&signup.with_errors(&errors))
+        .status(StatusCode::UNPROCESSABLE_ENTITY)
+        .try_into_http()
+        .unwrap();
+
+    assert_eq!(StatusCode::UNPROCESSABLE_ENTITY, response.status());
+    assert_eq!(html, response.body());
+
+    let mut without_errors = String::new();
+    signup
+        .try_write_form(&mut without_errors, &ValidationErrors::new())
+        .unwrap();
+
+    assert_eq!(signup.to_htmx(), without_errors);
+    assert!(!without_errors.contains("aria-invalid"));
+}
+
+#[test]
+fn validation_errors_in_oob_and_fragments() {
+    let signup = serde_urlencoded::from_str::<Signup>("email=foo%40").unwrap();
+    let errors = ValidationErrors::new().with("email", "Invalid email");
+
+    let controls = concat!(
+        r#"<label>Email<input type="email" name="email" value="foo@" required aria-invalid="true" aria-describedby="email-error"></label>"#,
+        r#"<span id="email-error" class="error">Invalid email</span>"#,
+        r#"<label>Age<input type="number" name="age"></label>"#,
+        r#"<label>Terms<input type="checkbox" name="terms" value="true"></label>"#,
+        r#"<label>Role<select name="role">"#,
+        r#"<option value=""></option><option value="Low">Low</option><option value="High">High</option>"#,
+        r#"</select></label>"#,
+        r#"</form>"#
+    );
+
+    let response = HtmxResponse::empty()
+        .oob(&signup.with_errors(&errors), OobSwap::True)
+        .try_into_http()
+        .unwrap();
+
+    assert_eq!(
+        format!(r#"<form hx-post="/signup" id="signup" hx-swap-oob="true">{controls}"#),
+        *response.body()
+    );
+
+    assert_eq!(
+        Some(format!(r#"<form hx-post="/signup" id="signup">{controls}"#)),
+        signup.with_errors(&errors).to_htmx_fragment("signup")
+    );
+    assert_eq!(None, signup.with_errors(&errors).to_htmx_fragment("login"));
+}
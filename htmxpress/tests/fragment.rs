@@ -0,0 +1,237 @@
+use htmxpress::{Element, HtmxElement};
+
+#[derive(Element)]
+#[element("main")]
+#[attrs(id = "page")]
+struct Page {
+    #[element("h1")]
+    #[attrs(id = "title")]
+    title: &'static str,
+
+    #[element("p")]
+    #[attr("id" = "subtitle-{}", id)]
+    subtitle: Option<&'static str>,
+
+    #[nest]
+    #[element("section")]
+    #[attrs(id = "todos")]
+    todos: TodoList,
+
+    #[nest]
+    footer: Option<Footer>,
+
+    id: usize,
+}
+
+#[derive(Element)]
+#[element("ul")]
+struct TodoList {
+    #[list(nest)]
+    items: Vec<Todo>,
+}
+
+#[derive(Element)]
+#[element("li")]
+#[attr("id" = "todo-{}", id)]
+struct Todo {
+    #[element("span")]
+    name: &'static str,
+
+    id: usize,
+}
+
+#[derive(Element)]
+#[element("footer")]
+#[attrs(id = "footer")]
+struct Footer {
+    #[element("small")]
+    text: &'static str,
+}
+
+#[derive(Element)]
+enum Panel {
+    #[element("div")]
+    #[attrs(id = "empty")]
+    Empty,
+
+    #[element("div")]
+    Todos(#[nest] Todo),
+}
+
+fn page() -> Page {
+    Page {
+        title: "Todos",
+        subtitle: None,
+        todos: TodoList {
+            items: vec![
+                Todo {
+                    name: "Laundry",
+                    id: 41,
+                },
+                Todo {
+                    name: "Dishes",
+                    id: 42,
+                },
+            ],
+        },
+        footer: None,
+        id: 1,
+    }
+}
+
+#[test]
+fn root() {
+    let page = page();
+    assert_eq!(Some(page.to_htmx()), page.to_htmx_fragment("page"));
+}
+
+#[test]
+fn static_id() {
+    assert_eq!(
+        Some(r#"<h1 id="title">Todos</h1>"#.to_string()),
+        page().to_htmx_fragment("title")
+    );
+}
+
+#[test]
+fn nested_wrapper() {
+    assert_eq!(
+        Some(
+            concat!(
+                r#"<section id="todos"><ul>"#,
+                r#"<li id="todo-41"><span>Laundry</span></li>"#,
+                r#"<li id="todo-42"><span>Dishes</span></li>"#,
+                r#"</ul></section>"#
+            )
+            .to_string()
+        ),
+        page().to_htmx_fragment("todos")
+    );
+}
+
+#[test]
+fn list_items() {
+    assert_eq!(
+        Some(r#"<li id="todo-42"><span>Dishes</span></li>"#.to_string()),
+        page().to_htmx_fragment("todo-42")
+    );
+    assert_eq!(None, page().to_htmx_fragment("todo-4"));
+    assert_eq!(None, page().to_htmx_fragment("todo-420"));
+}
+
+#[test]
+fn optional() {
+    let mut page = page();
+
+    assert_eq!(None, page.to_htmx_fragment("subtitle-1"));
+    assert_eq!(None, page.to_htmx_fragment("footer"));
+
+    page.subtitle = Some("Things to do");
+    page.footer = Some(Footer { text: "Done" });
+
+    assert_eq!(
+        Some(r#"<p id="subtitle-1">Things to do</p>"#.to_string()),
+        page.to_htmx_fragment("subtitle-1")
+    );
+    assert_eq!(
+        Some(r#"<footer id="footer"><small>Done</small></footer>"#.to_string()),
+        page.to_htmx_fragment("footer")
+    );
+}
+
+#[test]
+fn enums() {
+    assert_eq!(
+        Some(r#"<div id="empty"></div>"#.to_string()),
+        Panel::Empty.to_htmx_fragment("empty")
+    );
+
+    let panel = Panel::Todos(Todo {
+        name: "Laundry",
+        id: 1,
+    });
+    assert_eq!(
+        Some(r#"<li id="todo-1"><span>Laundry</span></li>"#.to_string()),
+        panel.to_htmx_fragment("todo-1")
+    );
+    assert_eq!(None, panel.to_htmx_fragment("empty"));
+}
+
+#[test]
+fn missing() {
+    let mut html = String::new();
+    assert!(!page().try_write_htmx_fragment("nope", &mut html).unwrap());
+    assert!(html.is_empty());
+}
+
+#[derive(Element)]
+#[element("div")]
+struct Lists {
+    #[element("ul")]
+    #[attrs(id = "todos")]
+    #[list(nest)]
+    todos: Vec<Todo>,
+
+    #[element("li")]
+    #[attrs(class = "tag", id = "tags")]
+    #[list]
+    tags: Vec<&'static str>,
+}
+
+#[test]
+fn list_containers() {
+    let lists = Lists {
+        todos: vec![Todo {
+            name: "Laundry",
+            id: 41,
+        }],
+        tags: vec!["a", "b"],
+    };
+
+    assert_eq!(
+        Some(r#"<ul id="todos"><li id="todo-41"><span>Laundry</span></li></ul>"#.to_string()),
+        lists.to_htmx_fragment("todos")
+    );
+    assert_eq!(
+        Some(r#"<li class="tag" id="tags">a</li><li class="tag" id="tags">b</li>"#.to_string()),
+        lists.to_htmx_fragment("tags")
+    );
+    assert_eq!(
+        Some(r#"<li id="todo-41"><span>Laundry</span></li>"#.to_string()),
+        lists.to_htmx_fragment("todo-41")
+    );
+}
+
+#[derive(Element)]
+#[element("section")]
+#[attrs(id = "card")]
+struct Card {
+    #[element("p")]
+    #[attrs(id = "card")]
+    text: &'static str,
+}
+
+#[derive(Element)]
+enum CardVariant {
+    #[element("section")]
+    #[attrs(id = "card")]
+    Card {
+        #[element("p")]
+        #[attrs(id = "card")]
+        text: &'static str,
+    },
+}
+
+#[test]
+fn element_before_fields() {
+    let html = r#"<section id="card"><p id="card">Hi</p></section>"#.to_string();
+
+    assert_eq!(
+        Some(html.clone()),
+        Card { text: "Hi" }.to_htmx_fragment("card")
+    );
+    assert_eq!(
+        Some(html),
+        CardVariant::Card { text: "Hi" }.to_htmx_fragment("card")
+    );
+}
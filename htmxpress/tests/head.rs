@@ -0,0 +1,167 @@
+use std::fmt::Write;
+
+use htmxpress::{Element, Head, HeadItem, HtmxElement, HxRequest, Layout, Page, RenderError};
+use http::{HeaderMap, HeaderValue};
+
+#[derive(Element)]
+#[element("main")]
+#[head(title = "Todos", stylesheet = "/todos.css")]
+#[head(meta(name = "description", content = "All the todos"))]
+struct Todos {
+    #[list(nest)]
+    items: Vec<Todo>,
+
+    #[nest]
+    chart: Option<Chart>,
+}
+
+#[derive(Element)]
+#[element("div")]
+#[head(title = "Todo", stylesheet = "/todo.css", script = "/todo.js")]
+#[head(meta(name = "description", content = "A single todo"))]
+struct Todo {
+    #[element("span")]
+    name: &'static str,
+}
+
+#[derive(Element)]
+#[element("canvas")]
+#[head(script = "/chart.js", meta("http-equiv" = "refresh", content = "30"))]
+struct Chart;
+
+#[derive(Element)]
+enum Panel {
+    #[head(stylesheet = "/empty.css")]
+    #[element("p")]
+    Empty,
+
+    #[element("section")]
+    Todos(#[nest] Todos),
+}
+
+fn todos(chart: bool) -> Todos {
+    Todos {
+        items: vec![Todo { name: "Laundry" }, Todo { name: "Dishes" }],
+        chart: chart.then_some(Chart),
+    }
+}
+
+#[test]
+fn collects_and_deduplicates() {
+    let head = Head::collect(&todos(false));
+
+    assert_eq!(
+        &[
+            HeadItem::Title("Todos"),
+            HeadItem::Stylesheet("/todos.css"),
+            HeadItem::Meta(&[("name", "description"), ("content", "All the todos")]),
+            HeadItem::Stylesheet("/todo.css"),
+            HeadItem::Script("/todo.js"),
+        ],
+        head.items()
+    );
+}
+
+#[test]
+fn optional_nest() {
+    let head = Head::collect(&todos(true));
+
+    assert_eq!(
+        &[
+            HeadItem::Script("/chart.js"),
+            HeadItem::Meta(&[("http-equiv", "refresh"), ("content", "30")]),
+        ],
+        &head.items()[5..]
+    );
+}
+
+#[test]
+fn enums() {
+    assert_eq!(
+        &[HeadItem::Stylesheet("/empty.css")],
+        Head::collect(&Panel::Empty).items()
+    );
+    assert_eq!(
+        Head::collect(&todos(false)),
+        Head::collect(&Panel::Todos(todos(false)))
+    );
+}
+
+#[test]
+fn renders_items() {
+    let mut html = String::new();
+    Head::collect(&Chart).try_write_merge(&mut html).unwrap();
+
+    assert_eq!(
+        concat!(
+            r#"<head hx-head="merge">"#,
+            r#"<script src="/chart.js"></script>"#,
+            r#"<meta http-equiv="refresh" content="30">"#,
+            r#"</head>"#
+        ),
+        html
+    );
+
+    let mut html = String::new();
+    Head::new().try_write_merge(&mut html).unwrap();
+    assert!(html.is_empty());
+}
+
+struct Base;
+
+impl Layout for Base {
+    fn try_write_head(&self, out: &mut impl Write) -> Result<(), RenderError> {
+        out.write_str(r#"<meta charset="utf-8">"#)
+            .map_err(|_| RenderError::new("Base", None))
+    }
+
+    fn collect_head(&self, head: &mut Head) {
+        head.push(HeadItem::Title("App"));
+        head.push(HeadItem::Stylesheet("/app.css"));
+    }
+}
+
+#[derive(Element)]
+#[element("p")]
+#[head(script = "/p.js")]
+struct Paragraph {
+    #[element("span")]
+    text: &'static str,
+}
+
+#[test]
+fn full_page() {
+    let page = Page::new(Base, Paragraph { text: "Hello" });
+
+    assert_eq!(
+        concat!(
+            r#"<!DOCTYPE html><html><head><meta charset="utf-8">"#,
+            r#"<script src="/p.js"></script><title>App</title>"#,
+            r#"<link rel="stylesheet" href="/app.css"></head>"#,
+            r#"<body><p><span>Hello</span></p></body></html>"#
+        ),
+        page.to_htmx()
+    );
+}
+
+#[test]
+fn partial_response() {
+    let page = Page::new(Base, Paragraph { text: "Hello" });
+
+    let mut headers = HeaderMap::new();
+    headers.insert("hx-request", HeaderValue::from_static("true"));
+
+    let response = page
+        .response(&HxRequest::from(&headers))
+        .try_into_http()
+        .unwrap();
+
+    assert_eq!(
+        concat!(
+            r#"<head hx-head="merge"><script src="/p.js"></script><title>App</title>"#,
+            r#"<link rel="stylesheet" href="/app.css"></head>"#,
+            r#"<p><span>Hello</span></p>"#
+        ),
+        response.body()
+    );
+}
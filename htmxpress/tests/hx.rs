@@ -20,7 +20,7 @@ fn works() {
         my_p: "myp".to_string(),
     };
 
-    let html = r#"<div hx-post="/somewhere/hello" hx-push-url="hello"><p hx-get="/somewhere/else" hx-target="something" hx-swap="innerHtml">myp</p></div>"#;
+    let html = r#"<div hx-post="/somewhere/hello" hx-push-url="hello"><p hx-target="something" hx-swap="innerHtml" hx-get="/somewhere/else">myp</p></div>"#;
 
     assert_eq!(html, test.to_htmx());
 }
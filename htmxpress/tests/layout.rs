@@ -0,0 +1,108 @@
+use std::fmt::Write;
+
+use htmxpress::{Element, HtmxElement, HxRequest, Layout, Page, RenderError};
+use http::{HeaderMap, HeaderValue};
+
+#[derive(Element)]
+struct Head {
+    #[element("title")]
+    title: &'static str,
+}
+
+#[derive(Element)]
+#[element("nav")]
+struct Nav {
+    #[element("a")]
+    #[attrs(href = "/")]
+    home: &'static str,
+}
+
+struct Base {
+    title: &'static str,
+}
+
+impl Layout for Base {
+    fn try_write_head(&self, out: &mut impl Write) -> Result<(), RenderError> {
+        Head { title: self.title }.try_write_htmx(out)
+    }
+
+    fn try_write_body(
+        &self,
+        content: &impl HtmxElement,
+        out: &mut impl Write,
+    ) -> Result<(), RenderError> {
+        Nav { home: "Home" }.try_write_htmx(out)?;
+        content.try_write_htmx(out)
+    }
+
+    fn lang(&self) -> Option<&str> {
+        Some("en")
+    }
+}
+
+#[derive(Element)]
+#[element("main")]
+#[attrs(id = "main")]
+struct Content {
+    #[element("p")]
+    text: &'static str,
+}
+
+fn page() -> Page<Base, Content> {
+    Page::new(Base { title: "Todos" }, Content { text: "Hello" })
+}
+
+const FULL: &str = concat!(
+    r#"<!DOCTYPE html><html lang="en"><head><title>Todos</title></head><body>"#,
+    r#"<nav><a href="/">Home</a></nav><main id="main"><p>Hello</p></main>"#,
+    r#"</body></html>"#
+);
+
+const FRAGMENT: &str = r#"<main id="main"><p>Hello</p></main>"#;
+
+fn hx(headers: &[&'static str]) -> HxRequest {
+    let mut map = HeaderMap::new();
+    for header in headers {
+        map.insert(*header, HeaderValue::from_static("true"));
+    }
+    HxRequest::from(&map)
+}
+
+#[test]
+fn renders_full_page() {
+    assert_eq!(FULL, page().to_htmx());
+}
+
+#[test]
+fn full_page_without_htmx() {
+    let response = page().response(&hx(&[])).try_into_http().unwrap();
+
+    assert_eq!(FULL, response.body());
+    assert_eq!("HX-Request", response.headers()["vary"]);
+}
+
+#[test]
+fn fragment_with_htmx() {
+    let response = page()
+        .response(&hx(&["hx-request"]))
+        .try_into_http()
+        .unwrap();
+
+    assert_eq!(FRAGMENT, response.body());
+    assert_eq!("HX-Request", response.headers()["vary"]);
+}
+
+#[test]
+fn full_page_on_history_restore() {
+    let response = page()
+        .response(&hx(&["hx-request", "hx-history-restore-request"]))
+        .try_into_http()
+        .unwrap();
+
+    assert_eq!(FULL, response.body());
+}
+
+#[test]
+fn fragment_lookup() {
+    assert_eq!(Some(FRAGMENT.to_string()), page().to_htmx_fragment("main"));
+}
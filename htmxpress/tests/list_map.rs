@@ -0,0 +1,86 @@
+use std::collections::{BTreeMap, HashMap};
+
+use htmxpress::{Element, HtmxElement, Markup, PreEscaped};
+
+#[derive(Element)]
+#[element("div")]
+struct Glossary {
+    #[element("dl")]
+    #[attrs(id = "terms")]
+    #[list(map)]
+    terms: BTreeMap<&'static str, &'static str>,
+
+    #[element("table")]
+    #[list(
+        map,
+        key = "th",
+        value = "td",
+        entry = "tr",
+        value_format = "{value} ({key})"
+    )]
+    counts: Vec<(&'static str, usize)>,
+
+    #[element("ul")]
+    #[list(map, key = "b", value = "i", key_format = "{key}:")]
+    single: HashMap<&'static str, &'static str>,
+}
+
+fn glossary() -> Glossary {
+    Glossary {
+        terms: BTreeMap::from([("rust", "<language>"), ("htmx", "library")]),
+        counts: vec![("foo", 1), ("bar", 2)],
+        single: HashMap::from([("key", "value")]),
+    }
+}
+
+#[test]
+fn key_value_pairs() {
+    assert_eq!(
+        concat!(
+            r#"<div><dl id="terms"><dt>htmx</dt><dd>library</dd><dt>rust</dt><dd>&lt;language&gt;</dd></dl>"#,
+            r#"<table><tr><th>foo</th><td>1 (foo)</td></tr><tr><th>bar</th><td>2 (bar)</td></tr></table>"#,
+            r#"<ul><b>key:</b><i>value</i></ul></div>"#
+        ),
+        glossary().to_htmx()
+    );
+}
+
+#[test]
+fn fragment() {
+    assert_eq!(
+        Some(r#"<dl id="terms"><dt>htmx</dt><dd>library</dd><dt>rust</dt><dd>&lt;language&gt;</dd></dl>"#.to_string()),
+        glossary().to_htmx_fragment("terms")
+    );
+}
+
+#[derive(Element)]
+#[element("dl")]
+struct Rendered {
+    #[list(map)]
+    snippets: BTreeMap<String, Markup>,
+
+    #[list(map, key = "b", value = "i")]
+    #[raw]
+    trusted: Vec<(&'static str, &'static str)>,
+}
+
+#[test]
+fn pre_escaped_keys_and_values() {
+    let rendered = Rendered {
+        snippets: BTreeMap::from([(
+            "<script>alert(1)</script>".to_string(),
+            PreEscaped("<em>safe</em>".to_string()),
+        )]),
+        trusted: vec![("<u>key</u>", "<s>value</s>")],
+    };
+
+    assert_eq!(
+        concat!(
+            r#"<dl>"#,
+            r#"<dt>&lt;script&gt;alert(1)&lt;/script&gt;</dt><dd><em>safe</em></dd>"#,
+            r#"<b><u>key</u></b><i><s>value</s></i>"#,
+            r#"</dl>"#
+        ),
+        rendered.to_htmx()
+    );
+}
@@ -0,0 +1,126 @@
+use htmxpress::{Element, HtmxElement, HtmxResponse, Oob, OobSwap, SwapStyle};
+
+#[derive(Element)]
+#[element("span")]
+#[attrs(id = "count")]
+struct Counter {
+    #[element("b")]
+    count: usize,
+}
+
+#[derive(Element)]
+#[element("li")]
+struct Item {
+    #[element("span")]
+    name: &'static str,
+}
+
+#[derive(Element)]
+#[element("div")]
+struct Page {
+    #[element("h1")]
+    title: &'static str,
+
+    #[nest]
+    counter: Oob<Counter>,
+}
+
+#[derive(Element)]
+enum Status {
+    #[element("p")]
+    #[attrs(id = "status")]
+    Ok,
+
+    #[element("p")]
+    #[attrs(id = "status", class = "error")]
+    Failed,
+}
+
+#[derive(Element)]
+struct Bare {
+    #[element("p")]
+    text: &'static str,
+}
+
+#[derive(Element)]
+#[element(transparent)]
+struct Wrapper(Counter);
+
+#[test]
+fn response_with_oob() {
+    let response = HtmxResponse::new(&Item { name: "Foo" })
+        .oob(&Counter { count: 2 }, OobSwap::True)
+        .oob(
+            &Item { name: "Bar" },
+            OobSwap::target(SwapStyle::BeforeEnd, "#list"),
+        )
+        .try_into_http()
+        .unwrap();
+
+    assert_eq!(
+        concat!(
+            r#"<li><span>Foo</span></li>"#,
+            r#"<span id="count" hx-swap-oob="true"><b>2</b></span>"#,
+            r##"<li hx-swap-oob="beforeend:#list"><span>Bar</span></li>"##,
+        ),
+        response.body()
+    );
+}
+
+#[test]
+fn nested_oob() {
+    let page = Page {
+        title: "Hello",
+        counter: Oob(Counter { count: 1 }, SwapStyle::InnerHtml.into()),
+    };
+
+    assert_eq!(
+        r#"<div><h1>Hello</h1><span id="count" hx-swap-oob="innerHTML"><b>1</b></span></div>"#,
+        page.to_htmx()
+    );
+}
+
+#[test]
+fn oob_only_on_root() {
+    let page = Page {
+        title: "Hello",
+        counter: Oob(Counter { count: 1 }, OobSwap::True),
+    };
+
+    let mut html = String::new();
+    page.try_write_htmx_oob(&mut html, &OobSwap::True).unwrap();
+
+    assert_eq!(
+        r#"<div hx-swap-oob="true"><h1>Hello</h1><span id="count" hx-swap-oob="true"><b>1</b></span></div>"#,
+        html
+    );
+}
+
+#[test]
+fn enum_variant_root() {
+    let oob = Oob(Status::Failed, OobSwap::True);
+    assert_eq!(
+        r#"<p id="status" class="error" hx-swap-oob="true"></p>"#,
+        oob.to_htmx()
+    );
+
+    assert_eq!(r#"<p id="status"></p>"#, Status::Ok.to_htmx());
+}
+
+#[test]
+fn transparent() {
+    let oob = Oob(Wrapper(Counter { count: 5 }), OobSwap::True);
+    assert_eq!(
+        r#"<span id="count" hx-swap-oob="true"><b>5</b></span>"#,
+        oob.to_htmx()
+    );
+}
+
+#[test]
+fn without_root_element() {
+    let bare = Bare { text: "text" };
+    assert_eq!("<p>text</p>", bare.to_htmx());
+
+    let err = Oob(bare, OobSwap::True).try_to_htmx().unwrap_err();
+    assert_eq!("failed to render Bare", err.to_string());
+}
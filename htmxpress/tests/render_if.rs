@@ -0,0 +1,118 @@
+use htmxpress::{Element, HtmxElement};
+
+#[derive(Element)]
+#[element("main")]
+struct Page {
+    #[element("h1")]
+    title: &'static str,
+
+    #[nest]
+    #[render_if(user.is_admin)]
+    toolbar: Toolbar,
+
+    #[list(nest)]
+    #[element("ul")]
+    items: Vec<Item>,
+
+    #[element("button")]
+    #[attrs(id = "more")]
+    #[skip_if(items.len() >= total)]
+    more: &'static str,
+
+    user: User,
+    total: usize,
+}
+
+struct User {
+    is_admin: bool,
+}
+
+#[derive(Element)]
+#[element("nav")]
+#[attrs(id = "toolbar")]
+struct Toolbar {
+    #[element("a")]
+    link: &'static str,
+}
+
+#[derive(Element)]
+#[element("li")]
+#[skip_if(self.hidden)]
+struct Item {
+    #[element("span")]
+    name: &'static str,
+    hidden: bool,
+}
+
+fn page(is_admin: bool, total: usize) -> Page {
+    Page {
+        title: "Items",
+        toolbar: Toolbar { link: "Settings" },
+        items: vec![
+            Item {
+                name: "foo",
+                hidden: false,
+            },
+            Item {
+                name: "bar",
+                hidden: true,
+            },
+        ],
+        more: "Load more",
+        user: User { is_admin },
+        total,
+    }
+}
+
+#[test]
+fn fields_and_structs() {
+    assert_eq!(
+        r#"<main><h1>Items</h1><nav id="toolbar"><a>Settings</a></nav><ul><li><span>foo</span></li></ul><button id="more">Load more</button></main>"#,
+        page(true, 3).to_htmx()
+    );
+
+    assert_eq!(
+        r#"<main><h1>Items</h1><ul><li><span>foo</span></li></ul></main>"#,
+        page(false, 2).to_htmx()
+    );
+
+    assert_eq!(
+        0,
+        Item {
+            name: "bar",
+            hidden: true
+        }
+        .size_hint()
+    );
+}
+
+#[test]
+fn skipped_fragments() {
+    let page = page(false, 2);
+
+    assert_eq!(None, page.to_htmx_fragment("toolbar"));
+    assert_eq!(None, page.to_htmx_fragment("more"));
+}
+
+#[derive(Element)]
+enum Message {
+    #[element("p")]
+    #[render_if(!text.is_empty())]
+    Text {
+        #[element("span")]
+        text: &'static str,
+    },
+
+    #[element("hr")]
+    Divider,
+}
+
+#[test]
+fn variants() {
+    assert_eq!(
+        "<p><span>hi</span></p>",
+        Message::Text { text: "hi" }.to_htmx()
+    );
+    assert_eq!("", Message::Text { text: "" }.to_htmx());
+    assert_eq!("<hr>", Message::Divider.to_htmx());
+}
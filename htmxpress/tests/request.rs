@@ -0,0 +1,61 @@
+use htmxpress::HxRequest;
+use http::{HeaderMap, HeaderValue, Request};
+
+#[test]
+fn parses_headers() {
+    let (parts, _) = Request::builder()
+        .uri("/items")
+        .header("HX-Request", "true")
+        .header("HX-Boosted", "true")
+        .header("HX-Current-URL", "http://localhost:3000/items?page=2")
+        .header("HX-Prompt", "yes")
+        .header("HX-Target", "list")
+        .header("HX-Trigger", "load-more")
+        .header("HX-Trigger-Name", "page")
+        .body(())
+        .unwrap()
+        .into_parts();
+
+    let hx = HxRequest::from(&parts);
+
+    assert!(hx.is_htmx());
+    assert!(hx.is_boosted());
+    assert!(!hx.is_history_restore_request());
+    assert_eq!("/items", hx.current_url().map(|url| url.path()).unwrap());
+    assert_eq!(Some("yes"), hx.prompt());
+    assert_eq!(Some("list"), hx.target());
+    assert_eq!(Some("load-more"), hx.trigger());
+    assert_eq!(Some("page"), hx.trigger_name());
+}
+
+#[test]
+fn missing_headers() {
+    let hx = HxRequest::from_headers(&HeaderMap::new());
+
+    assert_eq!(HxRequest::default(), hx);
+    assert!(!hx.is_htmx());
+    assert!(hx.current_url().is_none());
+    assert!(hx.target().is_none());
+}
+
+#[test]
+fn malformed_headers() {
+    let mut headers = HeaderMap::new();
+    headers.insert("hx-request", HeaderValue::from_static("yes"));
+    headers.insert(
+        "hx-history-restore-request",
+        HeaderValue::from_static("true"),
+    );
+    headers.insert("hx-current-url", HeaderValue::from_static("not a url"));
+    headers.insert(
+        "hx-prompt",
+        HeaderValue::from_bytes(b"caf\xc3\xa9").unwrap(),
+    );
+
+    let hx = HxRequest::from_headers(&headers);
+
+    assert!(!hx.is_htmx());
+    assert!(hx.is_history_restore_request());
+    assert!(hx.current_url().is_none());
+    assert_eq!(Some("café"), hx.prompt());
+}
@@ -0,0 +1,151 @@
+use std::fmt::{self, Display};
+
+use htmxpress::{
+    Element, HtmxElement, HtmxResponse, HxEvent, HxLocation, ResponseError, SwapStyle,
+};
+use http::{StatusCode, Uri};
+
+#[derive(Element)]
+#[element("div")]
+#[hx_get("/items")]
+struct Item {
+    #[element("p")]
+    name: &'static str,
+}
+
+#[test]
+fn renders_body() {
+    let response = Item { name: "Foo" }
+        .htmx_response()
+        .status(StatusCode::CREATED)
+        .try_into_http()
+        .unwrap();
+
+    assert_eq!(StatusCode::CREATED, response.status());
+    assert_eq!("text/html", response.headers()["content-type"]);
+    assert_eq!(r#"<div hx-get="/items"><p>Foo</p></div>"#, response.body());
+}
+
+#[test]
+fn triggers_without_detail() {
+    let response = HtmxResponse::empty()
+        .trigger("first")
+        .trigger(HxEvent::new("second"))
+        .trigger_after_settle("settled")
+        .try_into_http()
+        .unwrap();
+
+    assert_eq!("first, second", response.headers()["hx-trigger"]);
+    assert_eq!("settled", response.headers()["hx-trigger-after-settle"]);
+    assert!(response.headers().get("hx-trigger-after-swap").is_none());
+}
+
+#[test]
+fn triggers_with_detail() {
+    let response = HtmxResponse::empty()
+        .trigger_after_swap(HxEvent::new("show").detail("message", "Héllo \"world\""))
+        .trigger_after_swap("hide")
+        .try_into_http()
+        .unwrap();
+
+    assert_eq!(
+        r#"{"show":{"message":"H\u00e9llo \"world\""},"hide":{}}"#,
+        response.headers()["hx-trigger-after-swap"]
+    );
+}
+
+#[test]
+fn url_headers() {
+    let response = HtmxResponse::empty()
+        .push_url(Uri::from_static("/items?page=2"))
+        .no_replace_url()
+        .redirect(Uri::from_static("https://example.com/login"))
+        .refresh()
+        .try_into_http()
+        .unwrap();
+
+    assert_eq!("/items?page=2", response.headers()["hx-push-url"]);
+    assert_eq!("false", response.headers()["hx-replace-url"]);
+    assert_eq!(
+        "https://example.com/login",
+        response.headers()["hx-redirect"]
+    );
+    assert_eq!("true", response.headers()["hx-refresh"]);
+}
+
+#[test]
+fn swap_headers() {
+    let response = HtmxResponse::empty()
+        .retarget("#list")
+        .reswap(SwapStyle::OuterHtml)
+        .reselect(".item")
+        .try_into_http()
+        .unwrap();
+
+    assert_eq!("#list", response.headers()["hx-retarget"]);
+    assert_eq!("outerHTML", response.headers()["hx-reswap"]);
+    assert_eq!(".item", response.headers()["hx-reselect"]);
+}
+
+#[test]
+fn location() {
+    let response = HtmxResponse::empty()
+        .location(Uri::from_static("/items"))
+        .try_into_http()
+        .unwrap();
+
+    assert_eq!("/items", response.headers()["hx-location"]);
+
+    let location = HxLocation::new(Uri::from_static("/items"))
+        .target("#content")
+        .swap(SwapStyle::BeforeEnd)
+        .value("page", "2");
+
+    let response = HtmxResponse::empty()
+        .location(location)
+        .try_into_http()
+        .unwrap();
+
+    assert_eq!(
+        r##"{"path":"/items","target":"#content","swap":"beforeend","values":{"page":"2"}}"##,
+        response.headers()["hx-location"]
+    );
+}
+
+#[test]
+fn invalid_header() {
+    let err = HtmxResponse::empty()
+        .retarget("#list\n")
+        .try_into_http()
+        .unwrap_err();
+
+    assert_eq!(
+        ResponseError::InvalidHeader(htmxpress::headers::HX_RETARGET),
+        err
+    );
+}
+
+struct Faulty;
+
+impl Display for Faulty {
+    fn fmt(&self, _: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Err(fmt::Error)
+    }
+}
+
+#[derive(Element)]
+#[element("p")]
+struct Broken {
+    #[element("span")]
+    faulty: Faulty,
+}
+
+#[test]
+fn render_error() {
+    let err = HtmxResponse::new(&Broken { faulty: Faulty })
+        .try_into_http()
+        .unwrap_err();
+
+    assert!(matches!(err, ResponseError::Render(_)));
+    assert_eq!("failed to render Broken.faulty", err.to_string());
+}
@@ -0,0 +1,48 @@
+use htmxpress::{Element, HtmxElement};
+
+#[derive(Element)]
+#[element("ul")]
+#[attrs(class = "list")]
+struct List {
+    #[list]
+    #[element("li")]
+    items: Vec<&'static str>,
+
+    #[nest]
+    footer: Option<Footer>,
+}
+
+#[derive(Element)]
+#[element("footer")]
+struct Footer {
+    #[element("p")]
+    text: &'static str,
+}
+
+#[test]
+fn accounts_for_static_markup() {
+    let list = List {
+        items: vec!["foo", "bar", "qux"],
+        footer: None,
+    };
+
+    let outer = r#"<ul class="list"></ul>"#.len();
+    let item = "<li></li>".len();
+
+    assert_eq!(
+        Footer { text: "" }.size_hint(),
+        "<footer><p></p></footer>".len() + 16
+    );
+    assert_eq!(list.size_hint(), outer + 3 * (item + 16));
+    assert!(list.size_hint() >= list.to_htmx().len());
+
+    let list = List {
+        footer: Some(Footer { text: "foo" }),
+        ..list
+    };
+
+    assert_eq!(
+        list.size_hint(),
+        outer + 3 * (item + 16) + Footer { text: "" }.size_hint()
+    );
+}
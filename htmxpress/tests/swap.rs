@@ -0,0 +1,77 @@
+use std::time::Duration;
+
+use htmxpress::{Element, HtmxElement, HtmxResponse, HxSwap, Scroll, ScrollPosition, SwapStyle};
+
+#[derive(Element)]
+#[element("div")]
+#[hx_get("/items")]
+#[hx_swap(outerHTML swap:1s settle:1.5s scroll:top show:"#item > p":bottom)]
+struct Items {
+    #[element("button")]
+    #[hx_delete("/items")]
+    #[hx_swap(delete transition:true focus-scroll:false ignoreTitle:true)]
+    delete: &'static str,
+
+    #[element("a")]
+    #[hx_swap(innerHTML show:window:top scroll:"main":bottom swap:100ms)]
+    link: &'static str,
+}
+
+#[derive(Element)]
+enum Panel {
+    #[element("p")]
+    #[hx_swap(beforeend show:none)]
+    Empty,
+}
+
+#[test]
+fn validated_at_compile_time() {
+    let items = Items {
+        delete: "Delete",
+        link: "Link",
+    };
+
+    let html = concat!(
+        r#"<div hx-get="/items" hx-swap="outerHTML swap:1s settle:1.5s scroll:top show:#item &gt; p:bottom">"#,
+        r#"<button hx-delete="/items" hx-swap="delete transition:true focus-scroll:false ignoreTitle:true">Delete</button>"#,
+        r#"<a hx-swap="innerHTML show:window:top scroll:main:bottom swap:100ms">Link</a>"#,
+        r#"</div>"#
+    );
+
+    assert_eq!(html, items.to_htmx());
+    assert_eq!(
+        r#"<p hx-swap="beforeend show:none"></p>"#,
+        Panel::Empty.to_htmx()
+    );
+}
+
+#[test]
+fn runtime() {
+    assert_eq!("innerHTML", HxSwap::default().to_string());
+
+    let swap = HxSwap::new(SwapStyle::AfterBegin)
+        .swap(Duration::from_millis(1500))
+        .settle(Duration::from_secs(2))
+        .scroll(ScrollPosition::Bottom)
+        .show(Scroll::None)
+        .focus_scroll(true)
+        .ignore_title(false);
+
+    assert_eq!(
+        "afterbegin swap:1500ms settle:2s scroll:bottom show:none focus-scroll:true ignoreTitle:false",
+        swap.to_string()
+    );
+
+    let swap = HxSwap::new(SwapStyle::InnerHtml).scroll(("window", ScrollPosition::Top));
+    assert_eq!("innerHTML scroll:window:top", swap.to_string());
+}
+
+#[test]
+fn reswap() {
+    let response = HtmxResponse::empty()
+        .reswap(HxSwap::new(SwapStyle::OuterHtml).transition(true))
+        .try_into_http()
+        .unwrap();
+
+    assert_eq!("outerHTML transition:true", response.headers()["hx-reswap"]);
+}
@@ -0,0 +1,77 @@
+use std::time::Duration;
+
+use htmxpress::{Element, HtmxElement, HxTrigger, TriggerQueue};
+
+#[derive(Element)]
+#[element("div")]
+#[hx_get("/items")]
+#[hx_trigger(load, htmx:afterSwap from:document, every 2s [isActive()])]
+struct Items {
+    #[element("span")]
+    #[hx_trigger(keyup[ctrlKey && shiftKey] changed delay:500ms throttle:1.5s, "my.event" once consume)]
+    search: &'static str,
+
+    #[element("button")]
+    #[hx_trigger(click["key == 'Enter'"] from:closest "form" target:"#btn" queue:last)]
+    submit: &'static str,
+
+    #[element("p")]
+    #[hx_trigger(intersect once root:".list" threshold:0.5, revealed, my-event from:next)]
+    image: &'static str,
+}
+
+#[test]
+fn validated_at_compile_time() {
+    let items = Items {
+        search: "Search",
+        submit: "Submit",
+        image: "Image",
+    };
+
+    let html = concat!(
+        r#"<div hx-get="/items" hx-trigger="load, htmx:afterSwap from:document, every 2s[isActive()]">"#,
+        r#"<span hx-trigger="keyup[ctrlKey &amp;&amp; shiftKey] changed delay:500ms throttle:1.5s, my.event once consume">Search</span>"#,
+        r##"<button hx-trigger="click[key == &#39;Enter&#39;] from:closest form target:#btn queue:last">Submit</button>"##,
+        r#"<p hx-trigger="intersect once root:.list threshold:0.5, revealed, my-event from:next">Image</p>"#,
+        r#"</div>"#
+    );
+
+    assert_eq!(html, items.to_htmx());
+}
+
+#[test]
+fn runtime() {
+    assert_eq!("click", HxTrigger::event("click").to_string());
+
+    let trigger = HxTrigger::event("intersect")
+        .once()
+        .root(".list")
+        .threshold(0.5)
+        .or(HxTrigger::every(Duration::from_millis(1500)).filter("isActive()"))
+        .or(HxTrigger::event("submit")
+            .throttle(Duration::from_secs(1))
+            .target("#form")
+            .consume()
+            .queue(TriggerQueue::None));
+
+    assert_eq!(
+        "intersect once root:.list threshold:0.5, every 1500ms[isActive()], submit throttle:1s target:#form consume queue:none",
+        trigger.to_string()
+    );
+}
+
+#[derive(Element)]
+#[element("div")]
+#[attr("hx-trigger" = "{}", trigger)]
+struct Dynamic {
+    trigger: HxTrigger,
+}
+
+#[test]
+fn dynamic() {
+    let el = Dynamic {
+        trigger: HxTrigger::event("keyup").changed(),
+    };
+
+    assert_eq!(r#"<div hx-trigger="keyup changed"></div>"#, el.to_htmx());
+}
@@ -0,0 +1,61 @@
+use htmxpress::{Element, HtmxElement};
+
+#[derive(Element)]
+#[element("span")]
+#[attr("data-id" = "{}", 0)]
+struct UserId(#[element("b")] u64);
+
+#[derive(Element)]
+#[element("p")]
+struct Pair(
+    #[element("span")]
+    #[format("{} and {}", 1)]
+    &'static str,
+    &'static str,
+);
+
+#[derive(Element)]
+#[element("div")]
+#[attrs(class = "divider")]
+struct Divider;
+
+#[derive(Element)]
+#[element(transparent)]
+struct Wrapper(UserId);
+
+#[derive(Element)]
+#[element(transparent)]
+struct Named {
+    inner: Pair,
+}
+
+#[test]
+fn tuple_struct() {
+    assert_eq!(
+        r#"<span data-id="42"><b>42</b></span>"#,
+        UserId(42).to_htmx()
+    );
+    assert_eq!(
+        r#"<p><span>foo and bar</span></p>"#,
+        Pair("foo", "bar").to_htmx()
+    );
+}
+
+#[test]
+fn unit_struct() {
+    assert_eq!(r#"<div class="divider"></div>"#, Divider.to_htmx());
+    assert_eq!(Divider.to_htmx().len(), Divider.size_hint());
+}
+
+#[test]
+fn transparent() {
+    assert_eq!(UserId(1).to_htmx(), Wrapper(UserId(1)).to_htmx());
+    assert_eq!(UserId(1).size_hint(), Wrapper(UserId(1)).size_hint());
+    assert_eq!(
+        Pair("a", "b").to_htmx(),
+        Named {
+            inner: Pair("a", "b")
+        }
+        .to_htmx()
+    );
+}
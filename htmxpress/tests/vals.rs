@@ -0,0 +1,98 @@
+use std::collections::BTreeMap;
+
+use htmxpress::{Element, HtmxElement};
+use serde::Serialize;
+
+#[derive(Element)]
+#[element("div")]
+#[hx_post("/items")]
+#[hx_vals(id, "page-no" = page, name)]
+#[hx_headers("X-Csrf-Token" = token)]
+struct Item {
+    id: usize,
+    page: Option<usize>,
+    name: &'static str,
+    token: &'static str,
+}
+
+#[test]
+fn fields() {
+    let item = Item {
+        id: 1,
+        page: None,
+        name: r#"It's "quoted" <b>"#,
+        token: "abc",
+    };
+
+    let html = concat!(
+        r#"<div hx-post="/items" "#,
+        r#"hx-vals="{&quot;id&quot;:1,&quot;page-no&quot;:null,&quot;name&quot;:&quot;It&#39;s \&quot;quoted\&quot; &lt;b&gt;&quot;}" "#,
+        r#"hx-headers="{&quot;X-Csrf-Token&quot;:&quot;abc&quot;}"></div>"#
+    );
+
+    assert_eq!(html, item.to_htmx());
+}
+
+#[derive(Serialize)]
+struct Filter {
+    query: String,
+    tags: Vec<&'static str>,
+}
+
+#[derive(Element)]
+#[element("form")]
+#[hx_get("/search")]
+#[hx_vals(serde = filter)]
+struct Search {
+    filter: Filter,
+
+    #[nest]
+    submit: Button,
+}
+
+#[derive(Element)]
+#[element("button")]
+#[hx_vals(serde = 1)]
+#[hx_headers(serde = 2)]
+struct Button(
+    #[element("b")] &'static str,
+    BTreeMap<&'static str, usize>,
+    BTreeMap<&'static str, &'static str>,
+);
+
+#[test]
+fn serde() {
+    let search = Search {
+        filter: Filter {
+            query: "a&b".to_string(),
+            tags: vec!["x", "y"],
+        },
+        submit: Button(
+            "Go",
+            BTreeMap::from([("limit", 10)]),
+            BTreeMap::from([("X-Mode", "fast")]),
+        ),
+    };
+
+    let html = concat!(
+        r#"<form hx-get="/search" hx-vals="{&quot;query&quot;:&quot;a&amp;b&quot;,&quot;tags&quot;:[&quot;x&quot;,&quot;y&quot;]}">"#,
+        r#"<button hx-vals="{&quot;limit&quot;:10}" hx-headers="{&quot;X-Mode&quot;:&quot;fast&quot;}"><b>Go</b></button>"#,
+        r#"</form>"#
+    );
+
+    assert_eq!(html, search.to_htmx());
+}
+
+#[derive(Element)]
+#[element("div")]
+#[hx_vals(serde = 0)]
+struct Invalid(BTreeMap<(u8, u8), u8>);
+
+#[test]
+fn serialization_error() {
+    let err = Invalid(BTreeMap::from([((1, 2), 3)]))
+        .try_to_htmx()
+        .unwrap_err();
+
+    assert_eq!("failed to render Invalid", err.to_string());
+}
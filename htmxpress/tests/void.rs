@@ -0,0 +1,37 @@
+use htmxpress::{Element, HtmxElement};
+
+#[derive(Element)]
+#[element("form")]
+struct Form {
+    #[element("input")]
+    #[attr("type" = "text")]
+    #[attr("value" = "{}", title)]
+    #[attrs(name = "title")]
+    title: String,
+
+    #[element("br")]
+    _br: (),
+
+    #[element("img")]
+    #[attr("src" = "{}", avatar)]
+    avatar: &'static str,
+}
+
+#[derive(Element)]
+#[element("hr")]
+#[attrs(class = "divider")]
+struct Divider;
+
+#[test]
+fn void_elements_have_no_closing_tag() {
+    let form = Form {
+        title: r#"say "hi""#.to_string(),
+        _br: (),
+        avatar: "/avatar.png",
+    };
+
+    let html = r#"<form><input type="text" value="say &quot;hi&quot;" name="title"><br><img src="/avatar.png"></form>"#;
+
+    assert_eq!(html, form.to_htmx());
+    assert_eq!(r#"<hr class="divider">"#, Divider.to_htmx());
+}
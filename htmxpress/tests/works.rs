@@ -36,7 +36,7 @@ fn works() {
         },
     };
 
-    let htmx = r#"<div hx-post="/somewhere/something"><p hx-get="/somewhere/else">I am a p! Hello World!</p><div hx-get="/elsewhere" id="child" class="child-class"><p id="keepit69">Always keep it 69</p></div></div>"#;
+    let htmx = r#"<div hx-post="/somewhere/something"><p hx-get="/somewhere/else">I am a p! Hello World!</p><div id="child" class="child-class" hx-get="/elsewhere"><p id="keepit69">Always keep it 69</p></div></div>"#;
 
     assert_eq!(htmx, parent.to_htmx());
 }
@@ -0,0 +1,49 @@
+use htmxpress::{Element, HtmxElement};
+
+#[derive(Element)]
+#[element("ul")]
+struct List {
+    #[list(nest)]
+    items: Vec<Item>,
+}
+
+#[derive(Element)]
+#[element("li")]
+#[attr("id" = "item-{}", id)]
+struct Item {
+    id: usize,
+
+    #[nest]
+    child: Child,
+}
+
+#[derive(Element)]
+struct Child {
+    #[element("span")]
+    name: &'static str,
+}
+
+#[test]
+fn writes_into_existing_buffer() {
+    let list = List {
+        items: vec![
+            Item {
+                id: 1,
+                child: Child { name: "foo" },
+            },
+            Item {
+                id: 2,
+                child: Child { name: "bar" },
+            },
+        ],
+    };
+
+    let mut html = String::from("<main>");
+    list.write_htmx(&mut html).unwrap();
+    html.push_str("</main>");
+
+    let expected = r#"<main><ul><li id="item-1"><span>foo</span></li><li id="item-2"><span>bar</span></li></ul></main>"#;
+
+    assert_eq!(expected, html);
+    assert_eq!(&expected[6..expected.len() - 7], list.to_htmx());
+}
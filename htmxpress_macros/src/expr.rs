@@ -0,0 +1,194 @@
+//! Expressions written in attributes, such as the condition of `attr_bool`,
+//! which reference the fields of the element by their names.
+
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+use syn::{
+    parse::Parser, punctuated::Punctuated, visit_mut::VisitMut, Expr, Ident, Lit, Member, Pat,
+    Token,
+};
+
+use crate::markup::Markup;
+
+/// Resolve the fields referenced in the expression to place expressions accessing them,
+/// see [Markup::access].
+///
+/// Identifiers starting with a lowercase letter or an underscore are fields, e.g. `!is_open`
+/// or `count > 0`, while anything else such as constants, paths and called functions is kept
+/// as is. An integer literal on its own is a field of a tuple struct, e.g. `0`. Fields can also
+/// be accessed through `self`, e.g. `self.count`, which works in enum variants as well.
+///
+/// Fields are resolved in closures, blocks and the arguments of macros such as `matches!`,
+/// except where they are shadowed by a closure parameter or a binding of a pattern.
+pub fn resolve_fields(expr: &Expr, markup: &Markup) -> TokenStream {
+    if let Expr::Lit(ref lit) = expr {
+        if let Lit::Int(ref int) = lit.lit {
+            if let Ok(index) = int.base10_parse::<usize>() {
+                return markup.access(&Member::Unnamed(index.into()));
+            }
+        }
+    }
+
+    let mut expr = expr.clone();
+    FieldResolver {
+        markup,
+        shadowed: vec![],
+    }
+    .visit_expr_mut(&mut expr);
+    expr.into_token_stream()
+}
+
+struct FieldResolver<'a> {
+    markup: &'a Markup,
+
+    /// Bindings in scope, which take precedence over the fields with the same name
+    shadowed: Vec<Ident>,
+}
+
+impl FieldResolver<'_> {
+    /// Visit `f` with the bindings of the pattern in scope
+    fn scoped(&mut self, pat: &Pat, f: impl FnOnce(&mut Self)) {
+        let len = self.shadowed.len();
+        self.shadow(pat);
+        f(self);
+        self.shadowed.truncate(len);
+    }
+
+    fn shadow(&mut self, pat: &Pat) {
+        struct Bindings<'a>(&'a mut Vec<Ident>);
+
+        impl VisitMut for Bindings<'_> {
+            fn visit_pat_ident_mut(&mut self, pat: &mut syn::PatIdent) {
+                self.0.push(pat.ident.clone());
+                syn::visit_mut::visit_pat_ident_mut(self, pat);
+            }
+        }
+
+        Bindings(&mut self.shadowed).visit_pat_mut(&mut pat.clone());
+    }
+
+    /// Resolve the fields in the arguments of a macro, e.g. `matches!(state, State::Open)`
+    /// or `format!("{}", count)`, leaving it as is if they are not expressions.
+    ///
+    /// Only the scrutinee of `matches!` is resolved, since the rest is a pattern.
+    fn visit_macro_args(&mut self, mac: &mut syn::Macro) {
+        if mac.path.is_ident("matches") {
+            let parser = |input: syn::parse::ParseStream| {
+                let scrutinee: Expr = input.parse()?;
+                let rest: TokenStream = input.parse()?;
+                Ok((scrutinee, rest))
+            };
+
+            if let Ok((mut scrutinee, rest)) = parser.parse2(mac.tokens.clone()) {
+                self.visit_expr_mut(&mut scrutinee);
+                mac.tokens = quote!(#scrutinee #rest);
+            }
+            return;
+        }
+
+        let parser = Punctuated::<Expr, Token![,]>::parse_terminated;
+        if let Ok(mut args) = parser.parse2(mac.tokens.clone()) {
+            for arg in args.iter_mut() {
+                self.visit_expr_mut(arg);
+            }
+            mac.tokens = args.into_token_stream();
+        }
+    }
+}
+
+impl VisitMut for FieldResolver<'_> {
+    fn visit_expr_mut(&mut self, expr: &mut Expr) {
+        match expr {
+            Expr::Path(path) if path.qself.is_none() => {
+                let Some(ident) = path.path.get_ident() else {
+                    return;
+                };
+
+                let is_field = ident
+                    .to_string()
+                    .starts_with(|c: char| c.is_lowercase() || c == '_');
+
+                if is_field && ident != "self" && !self.shadowed.contains(ident) {
+                    let access = self.markup.access(&Member::Named(ident.clone()));
+                    *expr = syn::parse_quote!(#access);
+                }
+            }
+            // Enum variants have no `self` to access fields through
+            Expr::Field(field) if is_self(&field.base) => {
+                let access = self.markup.access(&field.member);
+                *expr = syn::parse_quote!(#access);
+            }
+            // Only the arguments of function calls can be fields
+            Expr::Call(call) => {
+                for arg in call.args.iter_mut() {
+                    self.visit_expr_mut(arg);
+                }
+            }
+            Expr::Macro(mac) => self.visit_macro_args(&mut mac.mac),
+            Expr::Closure(closure) => {
+                let len = self.shadowed.len();
+                for input in closure.inputs.iter() {
+                    self.shadow(input);
+                }
+                self.visit_expr_mut(&mut closure.body);
+                self.shadowed.truncate(len);
+            }
+            Expr::Match(mtch) => {
+                self.visit_expr_mut(&mut mtch.expr);
+                for arm in mtch.arms.iter_mut() {
+                    let pat = arm.pat.clone();
+                    self.scoped(&pat, |this| {
+                        if let Some((_, ref mut guard)) = arm.guard {
+                            this.visit_expr_mut(guard);
+                        }
+                        this.visit_expr_mut(&mut arm.body);
+                    });
+                }
+            }
+            Expr::If(expr_if) => {
+                let Expr::Let(ref mut expr_let) = *expr_if.cond else {
+                    syn::visit_mut::visit_expr_if_mut(self, expr_if);
+                    return;
+                };
+
+                self.visit_expr_mut(&mut expr_let.expr);
+                let pat = expr_let.pat.clone();
+                self.scoped(&pat, |this| this.visit_block_mut(&mut expr_if.then_branch));
+                if let Some((_, ref mut else_branch)) = expr_if.else_branch {
+                    self.visit_expr_mut(else_branch);
+                }
+            }
+            Expr::ForLoop(for_loop) => {
+                self.visit_expr_mut(&mut for_loop.expr);
+                let pat = for_loop.pat.clone();
+                self.scoped(&pat, |this| this.visit_block_mut(&mut for_loop.body));
+            }
+            _ => syn::visit_mut::visit_expr_mut(self, expr),
+        }
+    }
+
+    fn visit_block_mut(&mut self, block: &mut syn::Block) {
+        let len = self.shadowed.len();
+        for stmt in block.stmts.iter_mut() {
+            match stmt {
+                // Bindings are in scope for the statements after them
+                syn::Stmt::Local(local) => {
+                    if let Some(ref mut init) = local.init {
+                        self.visit_expr_mut(&mut init.expr);
+                        if let Some((_, ref mut diverge)) = init.diverge {
+                            self.visit_expr_mut(diverge);
+                        }
+                    }
+                    self.shadow(&local.pat);
+                }
+                syn::Stmt::Macro(stmt) => self.visit_macro_args(&mut stmt.mac),
+                _ => self.visit_stmt_mut(stmt),
+            }
+        }
+        self.shadowed.truncate(len);
+    }
+}
+
+fn is_self(expr: &Expr) -> bool {
+    matches!(expr, Expr::Path(path) if path.qself.is_none() && path.path.is_ident("self"))
+}
@@ -0,0 +1,575 @@
+//! The `#[form]` mode of `Element`, rendering a labelled control for every field
+//! without an element, and `#[derive(Select)]` for enums rendered as `<select>`.
+//!
+//! Names of the controls follow serde's `rename`, `rename_all` and `skip` attributes,
+//! so the submitted form deserializes into the same struct. Validation errors are
+//! looked up by those names from `errors` in the generated code.
+//!
+//! Controls whose submission would not deserialize back into the field are rejected,
+//! i.e. checkboxes without `serde(default)`, as unchecked ones are not submitted, and
+//! optional controls without `serde(deserialize_with)`, as empty ones are submitted
+//! as an empty string. `serde` is registered as a helper attribute of both derives
+//! so forms that are never deserialized can use it as well.
+
+use proc_macro2::TokenStream;
+use proc_macro_error::abort;
+use quote::quote;
+use syn::{ext::IdentExt, spanned::Spanned, Attribute, DeriveInput, LitStr, Member, Token};
+
+use crate::{escape_attr, field_member, markup::Markup, parse_str, INPUT_ATTR, LABEL_ATTR};
+
+/// Integer and float types rendered as `type="number"`
+const NUMBERS: [&str; 14] = [
+    "i8", "i16", "i32", "i64", "i128", "isize", "u8", "u16", "u32", "u64", "u128", "usize", "f32",
+    "f64",
+];
+
+/// Types rendered as `type="text"`
+const TEXT: [&str; 4] = ["String", "str", "char", "Cow"];
+
+/// serde's `rename_all` rules
+#[derive(Debug, Clone, Copy)]
+pub enum RenameRule {
+    Lower,
+    Upper,
+    Pascal,
+    Camel,
+    Snake,
+    ScreamingSnake,
+    Kebab,
+    ScreamingKebab,
+}
+
+impl RenameRule {
+    fn parse(lit: &LitStr) -> syn::Result<Self> {
+        match lit.value().as_str() {
+            "lowercase" => Ok(Self::Lower),
+            "UPPERCASE" => Ok(Self::Upper),
+            "PascalCase" => Ok(Self::Pascal),
+            "camelCase" => Ok(Self::Camel),
+            "snake_case" => Ok(Self::Snake),
+            "SCREAMING_SNAKE_CASE" => Ok(Self::ScreamingSnake),
+            "kebab-case" => Ok(Self::Kebab),
+            "SCREAMING-KEBAB-CASE" => Ok(Self::ScreamingKebab),
+            _ => Err(syn::Error::new(lit.span(), "unknown rename rule")),
+        }
+    }
+
+    /// Rename a field, written in snake case
+    pub fn apply_to_field(self, field: &str) -> String {
+        match self {
+            Self::Lower | Self::Snake => field.to_string(),
+            Self::Upper | Self::ScreamingSnake => field.to_ascii_uppercase(),
+            Self::Pascal => field.split('_').map(capitalize).collect(),
+            Self::Camel => {
+                let pascal = Self::Pascal.apply_to_field(field);
+                let mut chars = pascal.chars();
+                match chars.next() {
+                    Some(first) => first.to_ascii_lowercase().to_string() + chars.as_str(),
+                    None => pascal,
+                }
+            }
+            Self::Kebab => field.replace('_', "-"),
+            Self::ScreamingKebab => field.replace('_', "-").to_ascii_uppercase(),
+        }
+    }
+
+    /// Rename a variant, written in pascal case
+    pub fn apply_to_variant(self, variant: &str) -> String {
+        match self {
+            Self::Pascal => variant.to_string(),
+            Self::Lower => variant.to_ascii_lowercase(),
+            Self::Upper => variant.to_ascii_uppercase(),
+            Self::Camel => {
+                let mut chars = variant.chars();
+                match chars.next() {
+                    Some(first) => first.to_ascii_lowercase().to_string() + chars.as_str(),
+                    None => String::new(),
+                }
+            }
+            _ => {
+                let mut snake = String::new();
+                for (i, ch) in variant.char_indices() {
+                    if i > 0 && ch.is_uppercase() {
+                        snake.push('_');
+                    }
+                    snake.push(ch.to_ascii_lowercase());
+                }
+                self.apply_to_field(&snake)
+            }
+        }
+    }
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_ascii_uppercase().to_string() + chars.as_str(),
+        None => String::new(),
+    }
+}
+
+/// The serde attributes affecting the names of the controls and how they are deserialized
+#[derive(Debug, Default)]
+pub struct SerdeAttrs {
+    /// `rename = "..."` or `rename(deserialize = "...")`
+    pub rename: Option<String>,
+
+    /// `rename_all = "..."` or `rename_all(deserialize = "...")`
+    pub rename_all: Option<RenameRule>,
+
+    /// `skip` or `skip_deserializing`
+    pub skip: bool,
+
+    /// `default` or `default = "..."`
+    pub default: bool,
+
+    /// `deserialize_with = "..."` or `with = "..."`
+    pub deserialize_with: bool,
+}
+
+impl SerdeAttrs {
+    /// Collect the relevant parts of all `serde` attributes. Anything else,
+    /// including invalid attributes, is left for serde to report.
+    pub fn collect_from(attrs: &[Attribute]) -> Self {
+        let mut this = Self::default();
+
+        for attr in attrs.iter().filter(|attr| attr.path().is_ident("serde")) {
+            let _ = attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("rename") {
+                    if let Some(name) = deserialize_name(&meta)? {
+                        this.rename = Some(name.value());
+                    }
+                } else if meta.path.is_ident("rename_all") {
+                    if let Some(rule) = deserialize_name(&meta)? {
+                        this.rename_all =
+                            Some(RenameRule::parse(&rule).unwrap_or_else(|e| abort!(e.span(), e)));
+                    }
+                } else if meta.path.is_ident("skip") || meta.path.is_ident("skip_deserializing") {
+                    this.skip = true;
+                } else if meta.path.is_ident("default") {
+                    this.default = true;
+                    if meta.input.peek(Token![=]) {
+                        meta.value()?.parse::<syn::Expr>()?;
+                    }
+                } else if meta.path.is_ident("deserialize_with") || meta.path.is_ident("with") {
+                    this.deserialize_with = true;
+                    meta.value()?.parse::<syn::Expr>()?;
+                } else if meta.input.peek(Token![=]) {
+                    meta.value()?.parse::<syn::Expr>()?;
+                } else if meta.input.peek(syn::token::Paren) {
+                    let _content;
+                    syn::parenthesized!(_content in meta.input);
+                }
+                Ok(())
+            });
+        }
+
+        this
+    }
+}
+
+/// The name used when deserializing, from either `key = "name"`
+/// or `key(deserialize = "name")`
+fn deserialize_name(meta: &syn::meta::ParseNestedMeta) -> syn::Result<Option<LitStr>> {
+    if meta.input.peek(Token![=]) {
+        return Ok(Some(meta.value()?.parse()?));
+    }
+
+    let mut name = None;
+    meta.parse_nested_meta(|meta| {
+        let value = meta.value()?.parse::<LitStr>()?;
+        if meta.path.is_ident("deserialize") {
+            name = Some(value);
+        }
+        Ok(())
+    })?;
+
+    Ok(name)
+}
+
+/// A label written for humans from a field or variant name,
+/// i.e. `first_name` and `FirstName` both become `First name`
+fn default_label(name: &str) -> String {
+    let mut words = String::new();
+    for (i, ch) in name.char_indices() {
+        if ch == '_' {
+            words.push(' ');
+            continue;
+        }
+        if i > 0 && ch.is_uppercase() && !words.ends_with(' ') {
+            words.push(' ');
+        }
+        words.push(ch.to_ascii_lowercase());
+    }
+    capitalize(words.trim())
+}
+
+/// The control rendered for a field
+#[derive(Debug)]
+enum Control {
+    /// `bool`, a checkbox with the value `true`
+    Checkbox,
+
+    /// Integers and floats, floats allowing any step
+    Number { float: bool },
+
+    /// Any input type given with `input("type")`, `text` for strings
+    Input(String),
+
+    /// Any other type, rendered as a select using its `Select` implementation
+    Select(syn::Type),
+}
+
+/// The type wrapped in `Option` if the type is one
+fn option_inner(ty: &syn::Type) -> Option<&syn::Type> {
+    let syn::Type::Path(p) = ty else {
+        return None;
+    };
+    let seg = p.path.segments.last()?;
+    if seg.ident != "Option" {
+        return None;
+    }
+    let syn::PathArguments::AngleBracketed(ref args) = seg.arguments else {
+        return None;
+    };
+    match args.args.first()? {
+        syn::GenericArgument::Type(ty) => Some(ty),
+        _ => None,
+    }
+}
+
+impl Control {
+    fn from_type(ty: &syn::Type) -> Self {
+        let ty = match ty {
+            syn::Type::Reference(r) => &*r.elem,
+            ty => ty,
+        };
+
+        let ident = match ty {
+            syn::Type::Path(p) => p.path.segments.last().map(|seg| seg.ident.to_string()),
+            _ => None,
+        };
+
+        match ident.as_deref() {
+            Some("bool") => Self::Checkbox,
+            Some(number) if NUMBERS.contains(&number) => Self::Number {
+                float: number.starts_with('f'),
+            },
+            Some(text) if TEXT.contains(&text) => Self::Input("text".to_string()),
+            _ => Self::Select(ty.clone()),
+        }
+    }
+}
+
+/// Push the labelled control of a field without an element in a `#[form]` struct
+pub fn collect_control(field: &syn::Field, i: usize, container: &SerdeAttrs, markup: &mut Markup) {
+    let SerdeAttrs {
+        rename,
+        skip,
+        default,
+        deserialize_with,
+        ..
+    } = SerdeAttrs::collect_from(&field.attrs);
+
+    if skip {
+        return;
+    }
+
+    let member = field_member(field, i);
+    let Member::Named(ref ident) = member else {
+        abort!(
+            field.span(),
+            "`form` requires the struct to have named fields"
+        )
+    };
+    let ident = ident.unraw().to_string();
+
+    let key = rename.unwrap_or_else(|| match container.rename_all {
+        Some(rule) => rule.apply_to_field(&ident),
+        None => ident.clone(),
+    });
+    let name = escape_attr(&key);
+
+    let label = field
+        .attrs
+        .iter()
+        .find(|attr| attr.path().is_ident(LABEL_ATTR))
+        .map(parse_str)
+        .unwrap_or_else(|| default_label(&ident));
+
+    let (optional, ty) = match option_inner(&field.ty) {
+        Some(ty) => (true, ty),
+        None => (false, &field.ty),
+    };
+
+    let control = match field
+        .attrs
+        .iter()
+        .find(|attr| attr.path().is_ident(INPUT_ATTR))
+    {
+        Some(attr) => Control::Input(parse_str(attr)),
+        None => Control::from_type(ty),
+    };
+
+    let checkbox = matches!(control, Control::Checkbox);
+
+    if checkbox && !optional && !default && !container.default {
+        abort!(
+            field.span(),
+            "unchecked checkboxes are not submitted, so `{}` cannot be deserialized without a default",
+            ident;
+            help = "add `#[serde(default)]` to the field or the struct, or make it an `Option<bool>`"
+        )
+    }
+
+    if optional && !checkbox && !deserialize_with {
+        abort!(
+            field.span(),
+            "empty controls are submitted as an empty string, so `{}` cannot be deserialized as `None`",
+            ident;
+            help = r#"add `#[serde(default, deserialize_with = "htmxpress::empty_as_none")]` to the field"#
+        )
+    }
+
+    let hidden = matches!(control, Control::Input(ref ty) if ty == "hidden");
+    let required = if optional || hidden || checkbox {
+        ""
+    } else {
+        " required"
+    };
+
+    markup.set_field(Some(&member));
+    let access = markup.access(&member);
+
+    if !hidden {
+        markup.push_str(&format!("<label>{}", escape_attr(&label)));
+    }
+
+    match control {
+        Control::Checkbox => {
+            markup.push_str(&format!(
+                r#"<input type="checkbox" name="{name}" value="true""#
+            ));
+            let checked = if optional {
+                quote!(#access == Some(true))
+            } else {
+                quote!(#access)
+            };
+            let mut inner = markup.inner();
+            inner.push_str(" checked");
+            let tokens = inner.to_tokens();
+            markup.push_block(quote!(if #checked { #tokens }), quote!(8));
+            push_error_attrs(markup, &key, &name);
+            markup.push_str(">");
+        }
+        Control::Number { float } => {
+            markup.push_str(&format!(r#"<input type="number" name="{name}""#));
+            if float {
+                markup.push_str(r#" step="any""#);
+            }
+            push_value(markup, &access, optional);
+            markup.push_str(required);
+            push_error_attrs(markup, &key, &name);
+            markup.push_str(">");
+        }
+        Control::Input(ty) => {
+            markup.push_str(&format!(
+                r#"<input type="{}" name="{name}""#,
+                escape_attr(&ty)
+            ));
+            push_value(markup, &access, optional);
+            markup.push_str(required);
+            push_error_attrs(markup, &key, &name);
+            markup.push_str(">");
+        }
+        Control::Select(ty) => {
+            markup.push_str(&format!(r#"<select name="{name}"{required}"#));
+            push_error_attrs(markup, &key, &name);
+            markup.push_str(">");
+            if optional {
+                markup.push_str(r#"<option value=""></option>"#);
+            }
+
+            let selected = if optional {
+                quote!(#access.as_ref().map(|v| <#ty as htmxpress::Select>::value(v)) == Some(*value))
+            } else {
+                quote!(<#ty as htmxpress::Select>::value(&#access) == *value)
+            };
+
+            let mut selected_markup = markup.inner();
+            selected_markup.push_str(" selected");
+            let selected_tokens = selected_markup.to_tokens();
+
+            let mut option = markup.inner();
+            option.push_str(r#"<option value=""#);
+            option.push_write(
+                quote!(write!(html, "{}", htmxpress::escape::Attr(value))),
+                0,
+                1,
+            );
+            option.push_str(r#"""#);
+            option.push_block(quote!(if #selected { #selected_tokens }), quote!(9));
+            option.push_str(">");
+            option.push_write(
+                quote!(write!(html, "{}", htmxpress::escape::Text(label))),
+                0,
+                1,
+            );
+            option.push_str("</option>");
+            let tokens = option.to_tokens();
+            let size_hint = option.size_hint();
+
+            markup.push_block(
+                quote!(
+                    for (value, label) in <#ty as htmxpress::Select>::options() {
+                        #tokens
+                    }
+                ),
+                quote!(<#ty as htmxpress::Select>::options().len() * (#size_hint)),
+            );
+            markup.push_str("</select>");
+        }
+    }
+
+    if !hidden {
+        markup.push_str("</label>");
+    }
+
+    push_error_message(markup, &key, &name);
+}
+
+/// Push `aria-invalid` and `aria-describedby` referencing the error message,
+/// written only when `errors` in the generated code has an error for the control
+fn push_error_attrs(markup: &mut Markup, key: &str, name: &str) {
+    let mut attrs = markup.inner();
+    attrs.push_str(&format!(
+        r#" aria-invalid="true" aria-describedby="{name}-error""#
+    ));
+    let tokens = attrs.to_tokens();
+
+    markup.push_block(
+        quote!(
+            if errors.get(#key).is_some() {
+                #tokens
+            }
+        ),
+        quote!(0),
+    );
+}
+
+/// Push the error message of the control, see [push_error_attrs]
+fn push_error_message(markup: &mut Markup, key: &str, name: &str) {
+    let mut message = markup.inner();
+    message.push_str(&format!(r#"<span id="{name}-error" class="error">"#));
+    message.push_write(
+        quote!(write!(html, "{}", htmxpress::escape::Text(error))),
+        0,
+        1,
+    );
+    message.push_str("</span>");
+    let tokens = message.to_tokens();
+
+    markup.push_block(
+        quote!(
+            if let Some(error) = errors.get(#key) {
+                #tokens
+            }
+        ),
+        quote!(0),
+    );
+}
+
+/// Push ` value="..."`, only when the field is set if it is optional
+fn push_value(markup: &mut Markup, access: &TokenStream, optional: bool) {
+    let mut value = markup.inner();
+    value.push_str(r#" value=""#);
+    let field = if optional { quote!(el) } else { access.clone() };
+    value.push_write(
+        quote!(write!(html, "{}", htmxpress::escape::Attr(&#field))),
+        0,
+        1,
+    );
+    value.push_str(r#"""#);
+
+    if !optional {
+        markup.append(value);
+        return;
+    }
+
+    let tokens = value.to_tokens();
+    let size_hint = value.size_hint();
+    markup.push_block(
+        quote!(
+            if let Some(ref el) = #access {
+                #tokens
+            }
+        ),
+        quote!(
+            match #access {
+                Some(_) => #size_hint,
+                None => 0,
+            }
+        ),
+    );
+}
+
+/// Implement `Select` for an enum with unit variants
+pub fn derive_select(input: &DeriveInput) -> TokenStream {
+    let syn::Data::Enum(ref data) = input.data else {
+        abort!(input.span(), "Select can only be derived on enums")
+    };
+
+    let rename_all = SerdeAttrs::collect_from(&input.attrs).rename_all;
+
+    let mut options = vec![];
+    let mut arms = vec![];
+
+    for variant in data.variants.iter() {
+        if !matches!(variant.fields, syn::Fields::Unit) {
+            abort!(
+                variant.span(),
+                "Select can only be derived on enums with unit variants"
+            )
+        }
+
+        let SerdeAttrs { rename, skip, .. } = SerdeAttrs::collect_from(&variant.attrs);
+
+        let ident = &variant.ident;
+        let variant_name = ident.unraw().to_string();
+
+        let value = rename.unwrap_or_else(|| match rename_all {
+            Some(rule) => rule.apply_to_variant(&variant_name),
+            None => variant_name.clone(),
+        });
+
+        let label = variant
+            .attrs
+            .iter()
+            .find(|attr| attr.path().is_ident(LABEL_ATTR))
+            .map(parse_str)
+            .unwrap_or_else(|| default_label(&variant_name));
+
+        if !skip {
+            options.push(quote!((#value, #label)));
+        }
+        arms.push(quote!(Self::#ident => #value,));
+    }
+
+    let ident = &input.ident;
+    let (im, ty, wh) = input.generics.split_for_impl();
+
+    quote!(
+        impl #im htmxpress::Select for #ident #ty #wh {
+            fn options() -> &'static [(&'static str, &'static str)] {
+                &[#(#options),*]
+            }
+
+            fn value(&self) -> &'static str {
+                match self {
+                    #(#arms)*
+                }
+            }
+        }
+    )
+}
@@ -2,10 +2,19 @@ use std::fmt::Debug;
 
 use proc_macro2::{Span, TokenStream};
 use proc_macro_error::{abort, proc_macro_error};
-use quote::{format_ident, quote};
+use quote::quote;
+mod expr;
+mod form;
+mod list;
+mod markup;
+mod swap;
+mod trigger;
+mod vals;
+
+use markup::{binding, member_name, Markup, Receiver};
 use syn::{
     parse::ParseStream, punctuated::Punctuated, spanned::Spanned, Attribute, Data, DeriveInput,
-    Expr, Ident, LitStr, MetaList, MetaNameValue, Token,
+    Expr, Ident, LitStr, Member, MetaList, MetaNameValue, Token,
 };
 
 const ELEMENT_ATTR: &str = "element";
@@ -13,6 +22,8 @@ const NEST_ATTR: &str = "nest";
 const FORMAT_ATTR: &str = "format";
 const ATTRS_ATTR: &str = "attrs";
 const ATTR_ATTR: &str = "attr";
+const ATTR_BOOL_ATTR: &str = "attr_bool";
+const CLASS_ATTR: &str = "class";
 const LIST_ATTR: &str = "list";
 const HX_GET_ATTR: &str = "hx_get";
 const HX_POST_ATTR: &str = "hx_post";
@@ -25,6 +36,43 @@ const HX_ATTR: &str = "hx";
 const MAP_ATTR: &str = "map";
 const BEFORE_ATTR: &str = "before";
 const AFTER_ATTR: &str = "after";
+const RAW_ATTR: &str = "raw";
+const INTO_RESPONSE_ATTR: &str = "into_response";
+const RESPONDER_ATTR: &str = "responder";
+const HEAD_ATTR: &str = "head";
+const HX_SWAP_ATTR: &str = "hx_swap";
+const HX_TRIGGER_ATTR: &str = "hx_trigger";
+const HX_VALS_ATTR: &str = "hx_vals";
+const HX_HEADERS_ATTR: &str = "hx_headers";
+const FORM_ATTR: &str = "form";
+const INPUT_ATTR: &str = "input";
+const LABEL_ATTR: &str = "label";
+const RENDER_IF_ATTR: &str = "render_if";
+const SKIP_IF_ATTR: &str = "skip_if";
+
+/// Elements that cannot have any content and are written without a closing tag
+const VOID_ELEMENTS: [&str; 13] = [
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "source", "track",
+    "wbr",
+];
+
+/// Used as `element(transparent)` to forward rendering to the single field
+const TRANSPARENT: &str = "transparent";
+
+/// Types of `htmxpress` whose values are written without escaping, matched either by
+/// their name on its own or by their path in one of [PRE_ESCAPED_MODULES].
+///
+/// Types cannot be resolved by the macro, so `Markup` or `PreEscaped` on its own is
+/// assumed to be imported from `htmxpress`, even if it is an unrelated type or alias
+/// with the same name. Any other path, e.g. `mymod::Markup`, is escaped.
+const PRE_ESCAPED_TYPES: [&str; 2] = ["PreEscaped", "Markup"];
+
+/// Modules of `htmxpress` the [PRE_ESCAPED_TYPES] can be referenced through
+const PRE_ESCAPED_MODULES: [&[&str]; 2] = [&["htmxpress"], &["htmxpress", "escape"]];
+
+/// Containers looked through when checking for [PRE_ESCAPED_TYPES], since their
+/// items are what gets written, e.g. `Option<PreEscaped<String>>`
+const PRE_ESCAPED_CONTAINERS: [&str; 2] = ["Option", "Vec"];
 
 const HTMX_METHODS: [&str; 5] = [
     HX_GET_ATTR,
@@ -37,187 +85,951 @@ const HTMX_METHODS: [&str; 5] = [
 #[proc_macro_derive(
     Element,
     attributes(
-        element, list, attrs, attr, format, nest, urlencode, map, hx, hx_get, hx_post, hx_put,
-        hx_patch, hx_delete, default, before, after
+        element,
+        list,
+        attrs,
+        attr,
+        attr_bool,
+        class,
+        format,
+        nest,
+        urlencode,
+        map,
+        hx,
+        hx_get,
+        hx_post,
+        hx_put,
+        hx_patch,
+        hx_delete,
+        default,
+        before,
+        after,
+        raw,
+        into_response,
+        responder,
+        head,
+        hx_swap,
+        hx_trigger,
+        hx_vals,
+        hx_headers,
+        form,
+        input,
+        label,
+        render_if,
+        skip_if,
+        serde
     )
 )]
 #[proc_macro_error]
 pub fn derive_element(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
-    let strct: DeriveInput = syn::parse(input).expect("invalid input");
+    let input: DeriveInput = syn::parse(input).expect("invalid input");
+
+    let mut form = false;
+
+    let (tokens, size_hint, lookups, heads) = match input.data {
+        Data::Struct(_) => {
+            let htmx_struct = HtmxStruct::collect_from(&input);
+            let markup = htmx_struct.markup;
+            form = htmx_struct.form;
+            let lookups = markup.has_lookups().then(|| markup.lookups());
+            let heads = markup.has_heads().then(|| markup.heads());
+            (markup.to_tokens(), markup.size_hint(), lookups, heads)
+        }
+        Data::Enum(_) => {
+            if let Some(form) = find_flag(&input.attrs, FORM_ATTR) {
+                abort!(form.span(), "`form` can only be used on structs")
+            }
+            if let Some(attr) = input.attrs.iter().find(|attr| {
+                attr.path().is_ident(RENDER_IF_ATTR) || attr.path().is_ident(SKIP_IF_ATTR)
+            }) {
+                abort!(
+                    attr.span(),
+                    "`render_if` and `skip_if` cannot be used on enums, use them on the variants instead"
+                )
+            }
+            let htmx_enum = HtmxEnum::collect_from(&input);
+            (
+                htmx_enum.to_tokens(),
+                htmx_enum.size_hint(),
+                htmx_enum.lookups(),
+                htmx_enum.heads(),
+            )
+        }
+        Data::Union(_) => abort!(
+            input.span(),
+            "Element can only be derived on structs and enums"
+        ),
+    };
 
-    let HtmxStruct {
-        self_element,
-        inner_tokens,
-    } = HtmxStruct::collect_from(&strct);
+    let ident = &input.ident;
+    let (im, ty, wh) = input.generics.split_for_impl();
 
-    let ident = &strct.ident;
-    let (im, ty, wh) = strct.generics.split_for_impl();
+    let collect_head = heads.map(|heads| {
+        quote!(
+            fn collect_head(&self, head: &mut htmxpress::Head) {
+                #heads
+            }
+        )
+    });
 
-    let parent_open = self_element.open();
-    let parent_close = self_element.close();
+    let into_response = collect_into_response(&input);
+    let responder = collect_responder(&input);
+
+    // Forms are written with the validation errors of their controls
+    let (errors_param, errors_arg) = if form {
+        (
+            quote!(, errors: &htmxpress::ValidationErrors),
+            quote!(, &htmxpress::ValidationErrors::new()),
+        )
+    } else {
+        (quote!(), quote!())
+    };
+
+    // Elements without any ids to look up use the default implementation
+    let (write_fragment, fragment_impl) = match lookups {
+        Some(lookups) => (
+            Some(quote!(
+                #[doc(hidden)]
+                #[allow(unused_variables)]
+                fn __htmxpress_write_fragment(
+                    &self,
+                    id: &str,
+                    html: &mut impl ::core::fmt::Write
+                    #errors_param
+                ) -> ::core::result::Result<bool, htmxpress::RenderError> {
+                    #lookups
+                    Ok(false)
+                }
+            )),
+            Some(quote!(
+                fn try_write_htmx_fragment(
+                    &self,
+                    id: &str,
+                    html: &mut impl ::core::fmt::Write,
+                ) -> ::core::result::Result<bool, htmxpress::RenderError> {
+                    self.__htmxpress_write_fragment(id, html #errors_arg)
+                }
+            )),
+        ),
+        None => (None, None),
+    };
+
+    let impl_form = form.then(|| {
+        let form_fragment = write_fragment.is_some().then(|| {
+            quote!(
+                fn try_write_form_fragment(
+                    &self,
+                    id: &str,
+                    html: &mut impl ::core::fmt::Write,
+                    errors: &htmxpress::ValidationErrors,
+                ) -> ::core::result::Result<bool, htmxpress::RenderError> {
+                    self.__htmxpress_write_fragment(id, html, errors)
+                }
+            )
+        });
+
+        quote!(
+            impl #im htmxpress::Form for #ident #ty #wh {
+                fn try_write_form(
+                    &self,
+                    html: &mut impl ::core::fmt::Write,
+                    errors: &htmxpress::ValidationErrors,
+                ) -> ::core::result::Result<(), htmxpress::RenderError> {
+                    self.__htmxpress_write(html, None, errors)
+                }
+
+                fn try_write_form_oob(
+                    &self,
+                    html: &mut impl ::core::fmt::Write,
+                    oob: &htmxpress::OobSwap,
+                    errors: &htmxpress::ValidationErrors,
+                ) -> ::core::result::Result<(), htmxpress::RenderError> {
+                    self.__htmxpress_write(html, Some(oob), errors)
+                }
+
+                #form_fragment
+            }
+        )
+    });
 
     quote::quote!(
+        impl #im #ident #ty #wh {
+            /// Write the element, adding `hx-swap-oob` to the root element if `oob` is given
+            #[doc(hidden)]
+            #[allow(unused_variables)]
+            fn __htmxpress_write(
+                &self,
+                html: &mut impl ::core::fmt::Write,
+                oob: ::core::option::Option<&htmxpress::OobSwap>
+                #errors_param
+            ) -> ::core::result::Result<(), htmxpress::RenderError> {
+                #tokens
+                Ok(())
+            }
+
+            #write_fragment
+        }
+
         impl #im htmxpress::HtmxElement for #ident #ty #wh {
-            fn to_htmx(&self) -> String {
-                use std::fmt::Write;
-                let mut html = String::new();
-                #parent_open
-                #inner_tokens
-                #parent_close
-                html
+            fn try_write_htmx(
+                &self,
+                html: &mut impl ::core::fmt::Write,
+            ) -> ::core::result::Result<(), htmxpress::RenderError> {
+                self.__htmxpress_write(html, None #errors_arg)
+            }
+
+            fn try_write_htmx_oob(
+                &self,
+                html: &mut impl ::core::fmt::Write,
+                oob: &htmxpress::OobSwap,
+            ) -> ::core::result::Result<(), htmxpress::RenderError> {
+                self.__htmxpress_write(html, Some(oob) #errors_arg)
             }
+
+            fn size_hint(&self) -> usize {
+                #size_hint
+            }
+
+            #fragment_impl
+
+            #collect_head
         }
+
+        #impl_form
+        #into_response
+        #responder
     )
     .into()
 }
 
-#[derive(Debug, Default)]
+#[proc_macro_derive(Select, attributes(label, serde))]
+#[proc_macro_error]
+pub fn derive_select(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input: DeriveInput = syn::parse(input).expect("invalid input");
+    form::derive_select(&input).into()
+}
+
+/// Implement axum's `IntoResponse` when the element is annotated with `#[into_response]`.
+/// Requires the `axum` feature of htmxpress.
+fn collect_into_response(input: &DeriveInput) -> Option<TokenStream> {
+    find_flag(&input.attrs, INTO_RESPONSE_ATTR)?;
+
+    let ident = &input.ident;
+    let (im, ty, wh) = input.generics.split_for_impl();
+
+    Some(quote!(
+        impl #im htmxpress::axum::IntoResponse for #ident #ty #wh {
+            fn into_response(self) -> htmxpress::axum::Response {
+                htmxpress::axum::IntoResponse::into_response(htmxpress::Htmx(self))
+            }
+        }
+    ))
+}
+
+/// Implement actix-web's `Responder` when the element is annotated with `#[responder]`.
+/// Requires the `actix` feature of htmxpress.
+fn collect_responder(input: &DeriveInput) -> Option<TokenStream> {
+    find_flag(&input.attrs, RESPONDER_ATTR)?;
+
+    let ident = &input.ident;
+    let (im, ty, wh) = input.generics.split_for_impl();
+
+    Some(quote!(
+        impl #im htmxpress::actix::Responder for #ident #ty #wh {
+            type Body = htmxpress::actix::BoxBody;
+
+            fn respond_to(
+                self,
+                req: &htmxpress::actix::HttpRequest,
+            ) -> htmxpress::actix::HttpResponse<Self::Body> {
+                htmxpress::actix::Responder::respond_to(htmxpress::Htmx(self), req)
+            }
+        }
+    ))
+}
+
+/// Statements adding the items from all `head` attributes to `head` in the generated code
+fn collect_head_items(attrs: &[Attribute]) -> Option<TokenStream> {
+    let items = attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident(HEAD_ATTR))
+        .flat_map(|attr| {
+            let list = attr.meta.require_list().unwrap_or_else(|_| {
+                abort!(
+                    attr.meta.span(),
+                    r#"expected head items, e.g. `head(title = "Foo", stylesheet = "/style.css")`"#
+                )
+            });
+            list.parse_args_with(Punctuated::<HeadItem, Token![,]>::parse_terminated)
+                .unwrap_or_else(|e| abort!(e.span(), e))
+        })
+        .map(|item| match item {
+            HeadItem::Title(title) => quote!(htmxpress::HeadItem::Title(#title)),
+            HeadItem::Stylesheet(href) => quote!(htmxpress::HeadItem::Stylesheet(#href)),
+            HeadItem::Script(src) => quote!(htmxpress::HeadItem::Script(#src)),
+            HeadItem::Meta(attrs) => {
+                let attrs = attrs.iter().map(|(key, value)| quote!((#key, #value)));
+                quote!(htmxpress::HeadItem::Meta(&[#(#attrs),*]))
+            }
+        })
+        .collect::<Vec<_>>();
+
+    if items.is_empty() {
+        return None;
+    }
+
+    Some(quote!(#(head.push(#items);)*))
+}
+
+/// An item of `head(...)`
+#[derive(Debug)]
+enum HeadItem {
+    /// `title = "Foo"`
+    Title(LitStr),
+
+    /// `stylesheet = "/style.css"`
+    Stylesheet(LitStr),
+
+    /// `script = "/script.js"`
+    Script(LitStr),
+
+    /// `meta(name = "description", content = "Foo")`, with
+    /// the keys being either identifiers or string literals
+    Meta(Vec<(String, LitStr)>),
+}
+
+impl syn::parse::Parse for HeadItem {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let kind = input.parse::<Ident>()?;
+
+        if kind == "meta" {
+            let content;
+            syn::parenthesized!(content in input);
+
+            let attrs = Punctuated::<(String, LitStr), Token![,]>::parse_terminated_with(
+                &content,
+                |input| {
+                    let key = if input.peek(LitStr) {
+                        input.parse::<LitStr>()?.value()
+                    } else {
+                        input.parse::<Ident>()?.to_string()
+                    };
+                    input.parse::<Token![=]>()?;
+                    Ok((key, input.parse()?))
+                },
+            )?;
+
+            if attrs.is_empty() {
+                return Err(syn::Error::new(kind.span(), "meta requires attributes"));
+            }
+
+            return Ok(Self::Meta(attrs.into_iter().collect()));
+        }
+
+        input.parse::<Token![=]>()?;
+        let value = input.parse::<LitStr>()?;
+
+        match kind.to_string().as_str() {
+            "title" => Ok(Self::Title(value)),
+            "stylesheet" => Ok(Self::Stylesheet(value)),
+            "script" => Ok(Self::Script(value)),
+            _ => Err(syn::Error::new(
+                kind.span(),
+                "unknown head item, expected one of `title`, `meta`, `stylesheet` or `script`",
+            )),
+        }
+    }
+}
+
+/// Find an attribute that takes no arguments, e.g. `#[into_response]`
+fn find_flag<'a>(attrs: &'a [Attribute], name: &str) -> Option<&'a Attribute> {
+    let attr = attrs.iter().find(|attr| attr.path().is_ident(name))?;
+
+    if let Err(e) = attr.meta.require_path_only() {
+        abort!(e.span(), "`{}` does not take any arguments", name)
+    }
+
+    Some(attr)
+}
+
+#[derive(Debug)]
 struct HtmxStruct {
-    /// Self html element
-    self_element: HtmxStructElement,
+    /// The markup of the whole struct, obtained from
+    /// its own element, fields and nested htmx structs
+    markup: Markup,
 
-    /// Contains tokens for the HtmxElement impl
-    /// obtained from fields and nested htmx structs
-    inner_tokens: TokenStream,
+    /// Whether the struct is annotated with `form`
+    form: bool,
 }
 
 impl HtmxStruct {
     fn collect_from(strct: &DeriveInput) -> Self {
         let mut this = Self {
-            self_element: HtmxStructElement::collect_from(&strct.attrs),
-            ..Default::default()
+            markup: Markup::new(&strct.ident.to_string(), Receiver::SelfRef),
+            form: find_flag(&strct.attrs, FORM_ATTR).is_some(),
         };
 
-        let Data::Struct(ref strct) = strct.data else {
-            abort!(strct.span(), "Element can only be derived on structs");
+        let transparent = collect_transparent(&strct.attrs);
+        let condition = collect_render_condition(&strct.attrs, &this.markup);
+
+        if let Some(items) = collect_head_items(&strct.attrs) {
+            this.markup.push_head(items);
+        }
+
+        let Data::Struct(ref data) = strct.data else {
+            abort!(strct.span(), "expected struct");
         };
 
-        'fields: for field in strct.fields.iter() {
-            let mut optional = false;
-            if let syn::Type::Path(ref p) = field.ty {
-                if let Some(seg) = p.path.segments.first() {
-                    optional = seg.ident == "Option"
+        if let Some(span) = transparent {
+            if data.fields.len() != 1 {
+                abort!(
+                    span,
+                    "`element(transparent)` requires the struct to have exactly one field"
+                )
+            }
+
+            let member = field_member(data.fields.iter().next().unwrap(), 0);
+            let inner = this.markup.access(&member);
+
+            this.markup.push_lookup(quote!(
+                if htmxpress::HtmxElement::try_write_htmx_fragment(&#inner, id, html)? {
+                    return Ok(true);
                 }
+            ));
+            this.markup
+                .push_head(quote!(htmxpress::HtmxElement::collect_head(&#inner, head);));
+
+            this.markup.push_block(
+                quote!(match oob {
+                    Some(oob) => htmxpress::HtmxElement::try_write_htmx_oob(&#inner, html, oob)?,
+                    None => htmxpress::HtmxElement::try_write_htmx(&#inner, html)?,
+                }),
+                quote!(htmxpress::HtmxElement::size_hint(&#inner)),
+            );
+
+            if let Some(condition) = condition {
+                this.markup.render_if(condition);
             }
 
-            // Extract element from attributes
-            let element =
-                collect_htmx_field_el(field.ident.as_ref().unwrap(), &field.attrs, optional);
+            return this;
+        }
 
-            // Handle nested structs
-            for attr in field.attrs.iter() {
-                let Some(id) = attr.meta.path().get_ident() else {
-                    continue;
-                };
+        let self_element = if this.form {
+            HtmxStructElement::collect_form(&strct.attrs)
+        } else {
+            HtmxStructElement::collect_from(&strct.attrs)
+        };
+        check_void_content(&self_element, &data.fields);
+
+        if let Some(matches) = self_element.id_matches(&this.markup) {
+            // Forms are looked up with the errors given to the fragment
+            let errors = this.form.then(|| quote!(, errors));
+            this.markup.push_lookup(quote!(
+                if #matches {
+                    self.__htmxpress_write(html, None #errors)?;
+                    return Ok(true);
+                }
+            ));
+        }
 
-                if id == LIST_ATTR {
-                    match attr.meta.require_path_only() {
-                        Ok(_) => {
-                            if element.html_element.is_none() {
-                                abort!(
-                                    attr.meta.span(),
-                                    "list must have a corresponding element or nested struct"
-                                )
-                            }
-                            this.inner_tokens.extend(element.to_tokens(true));
+        self_element.open_root(&mut this.markup);
+        if this.form {
+            let container = form::SerdeAttrs::collect_from(&strct.attrs);
+            collect_form_fields(&data.fields, &container, &mut this.markup);
+        } else {
+            collect_fields(&data.fields, &mut this.markup);
+        }
+        self_element.close(&mut this.markup);
 
-                            continue 'fields;
-                        }
-                        Err(_) => {
-                            let Ok(list) = attr.meta.require_list() else {
-                                abort!(
-                                    attr.meta.span(),
-                                    "invalid list attribute, expected `list` or `list(nested)`"
-                                )
-                            };
-
-                            let Ok(ident) = list.parse_args::<Ident>() else {
-                                abort!(
-                                    attr.meta.span(),
-                                    "invalid list attribute, expected list(nested)"
-                                )
-                            };
-
-                            if ident != "nest" {
-                                abort!(
-                                    attr.meta.span(),
-                                    "invalid list attribute, expected list(nested)"
-                                )
-                            }
+        if let Some(condition) = condition {
+            this.markup.render_if(condition);
+        }
 
-                            let ident = field
-                                .ident
-                                .as_ref()
-                                .unwrap_or_else(|| abort!(field.ident.span(), "invalid field"));
-
-                            let open = element.open();
-                            let close = element.close();
-                            this.inner_tokens.extend(quote!(
-                                {
-                                    #open
-                                    for el in self.#ident.iter() {
-                                        let nested = el.to_htmx();
-                                        let _ = write!(html, "{nested}");
-                                    }
-                                    #close
-                                }
-                            ));
-
-                            continue 'fields;
+        this
+    }
+}
+
+#[derive(Debug)]
+struct HtmxEnum {
+    /// Name of the enum, used for errors
+    name: String,
+
+    /// The enum's own element, wrapping all variants
+    self_element: HtmxStructElement,
+
+    /// Attributes of the enum itself
+    attrs: Vec<Attribute>,
+
+    /// The match pattern and markup of each variant
+    variants: Vec<(TokenStream, Markup)>,
+}
+
+impl HtmxEnum {
+    fn collect_from(enm: &DeriveInput) -> Self {
+        let Data::Enum(ref data) = enm.data else {
+            abort!(enm.span(), "expected enum");
+        };
+
+        let self_element = HtmxStructElement::collect_from(&enm.attrs);
+
+        let variants = data
+            .variants
+            .iter()
+            .map(|variant| {
+                let name = format!("{}::{}", enm.ident, variant.ident);
+                let mut markup = Markup::new(&name, Receiver::Bindings);
+
+                if let Some(items) = collect_head_items(&variant.attrs) {
+                    markup.push_head(items);
+                }
+
+                let variant_element = HtmxStructElement::collect_from(&variant.attrs);
+                check_void_content(&variant_element, &variant.fields);
+
+                // The enum's own element is the root if it has one
+                let mut body = markup.inner();
+                if self_element.html_element.is_some() {
+                    variant_element.open(&mut body);
+                } else {
+                    variant_element.open_root(&mut body);
+                }
+                collect_fields(&variant.fields, &mut body);
+                variant_element.close(&mut body);
+
+                // The variant is checked before its fields, same as structs
+                if let Some(matches) = variant_element.id_matches(&body) {
+                    let tokens = body.to_tokens();
+                    markup.push_lookup(quote!(
+                        if #matches {
+                            let oob = ::core::option::Option::None::<&htmxpress::OobSwap>;
+                            #tokens
+                            return Ok(true);
                         }
+                    ));
+                }
+                markup.append(body);
+
+                if let Some(condition) = collect_render_condition(&variant.attrs, &markup) {
+                    markup.render_if(condition);
+                }
+
+                let ident = &variant.ident;
+                let bindings = variant.fields.iter().enumerate().map(|(i, field)| {
+                    let member = field_member(field, i);
+                    let binding = binding(&member);
+                    quote!(#member: #binding)
+                });
+
+                (quote!(Self::#ident { #(#bindings),* }), markup)
+            })
+            .collect();
+
+        Self {
+            name: enm.ident.to_string(),
+            self_element,
+            attrs: enm.attrs.clone(),
+            variants,
+        }
+    }
+
+    fn to_tokens(&self) -> TokenStream {
+        let mut open = Markup::new(&self.name, Receiver::SelfRef);
+        let mut close = Markup::new(&self.name, Receiver::SelfRef);
+        if self.self_element.html_element.is_some() {
+            self.self_element.open_root(&mut open);
+        }
+        self.self_element.close(&mut close);
+
+        let open = open.to_tokens();
+        let close = close.to_tokens();
+
+        let arms = self.variants.iter().map(|(pattern, markup)| {
+            let tokens = markup.to_tokens();
+            quote!(
+                #[allow(unused_variables)]
+                #pattern => {
+                    #tokens
+                }
+            )
+        });
+
+        quote!(
+            #open
+            match self {
+                #(#arms)*
+            }
+            #close
+        )
+    }
+
+    /// Statements searching for a fragment in the enum's element and its variants,
+    /// if any of them can contain one
+    fn lookups(&self) -> Option<TokenStream> {
+        let root = self
+            .self_element
+            .id_matches(&Markup::new(&self.name, Receiver::SelfRef))
+            .map(|matches| {
+                quote!(
+                    if #matches {
+                        htmxpress::HtmxElement::try_write_htmx(self, html)?;
+                        return Ok(true);
                     }
+                )
+            });
+
+        if root.is_none() && !self.variants.iter().any(|(_, markup)| markup.has_lookups()) {
+            return None;
+        }
+
+        let arms = self.variants.iter().map(|(pattern, markup)| {
+            let lookups = markup.lookups();
+            quote!(
+                #[allow(unused_variables)]
+                #pattern => {
+                    #lookups
                 }
+            )
+        });
+
+        Some(quote!(
+            #root
+            match self {
+                #(#arms)*
+            }
+        ))
+    }
+
+    /// Statements collecting the `<head>` items of the enum and its variants,
+    /// if any of them declare or can contain some
+    fn heads(&self) -> Option<TokenStream> {
+        let own = collect_head_items(&self.attrs);
+
+        if own.is_none() && !self.variants.iter().any(|(_, markup)| markup.has_heads()) {
+            return None;
+        }
+
+        let arms = self.variants.iter().map(|(pattern, markup)| {
+            let heads = markup.heads();
+            quote!(
+                #[allow(unused_variables)]
+                #pattern => {
+                    #heads
+                }
+            )
+        });
 
-                if id == NEST_ATTR {
-                    let field_name = field.ident.as_ref().unwrap_or_else(|| {
+        Some(quote!(
+            #own
+            match self {
+                #(#arms)*
+            }
+        ))
+    }
+
+    fn size_hint(&self) -> TokenStream {
+        let mut markup = Markup::new(&self.name, Receiver::SelfRef);
+        self.self_element.open(&mut markup);
+        self.self_element.close(&mut markup);
+        let size_hint = markup.size_hint();
+
+        let arms = self.variants.iter().map(|(pattern, markup)| {
+            let size_hint = markup.size_hint();
+            quote!(
+                #[allow(unused_variables)]
+                #pattern => #size_hint,
+            )
+        });
+
+        quote!(
+            #size_hint + match self {
+                #(#arms)*
+            }
+        )
+    }
+}
+
+/// The member used to access the field, i.e. its name
+/// or its index when the fields are unnamed
+fn field_member(field: &syn::Field, index: usize) -> Member {
+    match field.ident {
+        Some(ref ident) => Member::Named(ident.clone()),
+        None => Member::Unnamed(index.into()),
+    }
+}
+
+/// Push the markup of all the fields annotated with `element`, `nest` or `list`
+fn collect_fields(fields: &syn::Fields, markup: &mut Markup) {
+    markup.set_raw_fields(raw_fields(fields));
+    for (i, field) in fields.iter().enumerate() {
+        let mut field_markup = markup.inner();
+        collect_field(field, i, &mut field_markup);
+        if let Some(condition) = collect_render_condition(&field.attrs, markup) {
+            field_markup.render_if(condition);
+        }
+        markup.append(field_markup);
+    }
+}
+
+/// Push the markup of the fields of a `#[form]` struct. Fields annotated with `element`,
+/// `nest` or `list` are rendered as usual, while all others become labelled controls.
+fn collect_form_fields(fields: &syn::Fields, container: &form::SerdeAttrs, markup: &mut Markup) {
+    markup.set_raw_fields(raw_fields(fields));
+    for (i, field) in fields.iter().enumerate() {
+        let mut field_markup = markup.inner();
+
+        let element = field.attrs.iter().any(|attr| {
+            attr.path().is_ident(ELEMENT_ATTR)
+                || attr.path().is_ident(NEST_ATTR)
+                || attr.path().is_ident(LIST_ATTR)
+        });
+
+        if element {
+            collect_field(field, i, &mut field_markup);
+        } else {
+            form::collect_control(field, i, container, &mut field_markup);
+        }
+
+        if let Some(condition) = collect_render_condition(&field.attrs, markup) {
+            field_markup.render_if(condition);
+        }
+
+        markup.append(field_markup);
+    }
+}
+
+/// The fields written without escaping, either because they are pre-escaped or have `raw`
+fn raw_fields(fields: &syn::Fields) -> Vec<Member> {
+    fields
+        .iter()
+        .enumerate()
+        .filter(|(_, field)| {
+            is_pre_escaped(&field.ty) || find_flag(&field.attrs, RAW_ATTR).is_some()
+        })
+        .map(|(i, field)| field_member(field, i))
+        .collect()
+}
+
+/// Push the markup of a single field along with the lookups
+/// for finding fragments in it, see [Markup::push_lookup]
+fn collect_field(field: &syn::Field, i: usize, markup: &mut Markup) {
+    let mut optional = false;
+    if let syn::Type::Path(ref p) = field.ty {
+        if let Some(seg) = p.path.segments.first() {
+            optional = seg.ident == "Option"
+        }
+    }
+
+    let raw = is_pre_escaped(&field.ty);
+    let member = field_member(field, i);
+
+    // Extract element from attributes
+    let element = collect_htmx_field_el(&member, &field.attrs, optional, raw);
+
+    markup.set_field(Some(&member));
+
+    // Handle nested structs
+    for attr in field.attrs.iter() {
+        let Some(id) = attr.meta.path().get_ident() else {
+            continue;
+        };
+
+        if id == LIST_ATTR {
+            match attr.meta.require_path_only() {
+                Ok(_) => {
+                    if element.html_element.is_none() {
                         abort!(
-                            field.span(),
-                            "HtmxElement only works on structs with named fields"
+                            attr.meta.span(),
+                            "list must have a corresponding element or nested struct"
                         )
-                    });
+                    }
+                    element.write_to(markup, true);
+                    element.push_lookup(markup);
 
-                    let _self = if optional {
-                        quote!(#field_name)
-                    } else {
-                        quote!(&self.#field_name)
+                    return;
+                }
+                Err(_) => {
+                    let Ok(list) = attr.meta.require_list() else {
+                        abort!(
+                            attr.meta.span(),
+                            "invalid list attribute, expected `list`, `list(nested)` or `list(map)`"
+                        )
                     };
 
-                    let open = element.open();
-                    let close = element.close();
-
-                    let mut tokens = quote!(
-                        #open
-                        {
-                            let nested = #_self.to_htmx();
-                            let _ = write!(html, "{nested}");
+                    let is_map = list
+                        .tokens
+                        .clone()
+                        .into_iter()
+                        .next()
+                        .is_some_and(|token| token.to_string() == "map");
+
+                    if is_map {
+                        let map = list
+                            .parse_args::<list::MapList>()
+                            .unwrap_or_else(|e| abort!(e.span(), e));
+
+                        if let Some(attr) = field.attrs.iter().find(|attr| {
+                            attr.path().is_ident(FORMAT_ATTR) || attr.path().is_ident(MAP_ATTR)
+                        }) {
+                            abort!(
+                                attr.span(),
+                                "`list(map)` cannot be combined with `format` or `map`, use `key_format` and `value_format` instead"
+                            )
                         }
-                    );
 
-                    if optional {
-                        tokens = quote!(
-                            if let Some(ref #field_name) = self.#field_name {
-                                #tokens
+                        let access = markup.access(&member);
+
+                        element.open(markup);
+                        // Keys and values are escaped unless their own type is pre-escaped
+                        let raw = find_flag(&field.attrs, RAW_ATTR).is_some();
+                        let (key_raw, value_raw) = match list::pair_types(&field.ty) {
+                            Some((key, value)) => {
+                                (raw || is_pre_escaped(key), raw || is_pre_escaped(value))
                             }
+                            None => (raw, raw),
+                        };
+                        map.write_to(markup, &access, key_raw, value_raw);
+                        element.close(markup);
+                        element.push_lookup(markup);
+
+                        return;
+                    }
+
+                    let Ok(ident) = list.parse_args::<Ident>() else {
+                        abort!(
+                            attr.meta.span(),
+                            "invalid list attribute, expected list(nested)"
+                        )
+                    };
+
+                    if ident != "nest" {
+                        abort!(
+                            attr.meta.span(),
+                            "invalid list attribute, expected list(nested)"
                         )
                     }
 
-                    tokens = quote!(
-                        #tokens
-                        #close
+                    let strct = markup.strct();
+                    let field_str = member_name(&member);
+                    let _self = markup.access(&member);
+
+                    element.open(markup);
+                    markup.push_block(
+                        quote!(
+                            for el in #_self.iter() {
+                                htmxpress::HtmxElement::try_write_htmx(el, html)
+                                    .map_err(|e| e.within(#strct, Some(#field_str)))?;
+                            }
+                        ),
+                        quote!(#_self
+                            .iter()
+                            .map(htmxpress::HtmxElement::size_hint)
+                            .sum::<usize>()),
                     );
+                    element.close(markup);
+                    element.push_lookup(markup);
+
+                    markup.push_lookup(quote!(
+                        for el in #_self.iter() {
+                            if htmxpress::HtmxElement::try_write_htmx_fragment(el, id, html)
+                                .map_err(|e| e.within(#strct, Some(#field_str)))?
+                            {
+                                return Ok(true);
+                            }
+                        }
+                    ));
 
-                    this.inner_tokens.extend(tokens);
+                    markup.push_head(quote!(
+                        for el in #_self.iter() {
+                            htmxpress::HtmxElement::collect_head(el, head);
+                        }
+                    ));
 
-                    continue 'fields;
+                    return;
                 }
             }
+        }
+
+        if id == NEST_ATTR {
+            let _self = if optional {
+                quote!((*el))
+            } else {
+                markup.access(&member)
+            };
 
-            if element.html_element.is_some() {
-                this.inner_tokens.extend(element.to_tokens(false))
+            let strct = markup.strct();
+            let field_str = member_name(&member);
+
+            let write_nested = |markup: &mut Markup| {
+                element.open(markup);
+                markup.push_block(
+                    quote!(
+                        htmxpress::HtmxElement::try_write_htmx(&#_self, html)
+                            .map_err(|e| e.within(#strct, Some(#field_str)))?;
+                    ),
+                    quote!(htmxpress::HtmxElement::size_hint(&#_self)),
+                );
+                element.close(markup);
+            };
+
+            if optional {
+                let access = markup.access(&member);
+                let mut inner = markup.inner();
+                write_nested(&mut inner);
+                let tokens = inner.to_tokens();
+                let size_hint = inner.size_hint();
+                markup.push_block(
+                    quote!(
+                        if let Some(ref el) = #access {
+                            #tokens
+                        }
+                    ),
+                    quote!(
+                        match #access {
+                            Some(ref el) => #size_hint,
+                            None => 0,
+                        }
+                    ),
+                );
+            } else {
+                write_nested(markup);
+            }
+
+            element.push_lookup(markup);
+
+            let find = quote!(
+                if htmxpress::HtmxElement::try_write_htmx_fragment(&#_self, id, html)
+                    .map_err(|e| e.within(#strct, Some(#field_str)))?
+                {
+                    return Ok(true);
+                }
+            );
+
+            let collect = quote!(htmxpress::HtmxElement::collect_head(&#_self, head););
+
+            if optional {
+                let access = markup.access(&member);
+                markup.push_lookup(quote!(
+                    if let Some(ref el) = #access {
+                        #find
+                    }
+                ));
+                markup.push_head(quote!(
+                    if let Some(ref el) = #access {
+                        #collect
+                    }
+                ));
+            } else {
+                markup.push_lookup(find);
+                markup.push_head(collect);
             }
+
+            return;
         }
+    }
 
-        this
+    if element.html_element.is_some() {
+        element.write_to(markup, false);
+        element.push_lookup(markup);
     }
 }
 
@@ -233,8 +1045,94 @@ struct HtmlElement {
     after: Vec<String>,
 }
 
+/// Check for `element(transparent)`, returning its span if present.
+///
+/// Aborts if any other HTML related attributes are found along with it.
+fn collect_transparent(attrs: &[Attribute]) -> Option<Span> {
+    let transparent = attrs.iter().find_map(|attr| {
+        if !attr.path().is_ident(ELEMENT_ATTR) {
+            return None;
+        }
+        let ident = attr.meta.require_list().ok()?.parse_args::<Ident>().ok()?;
+        if ident != TRANSPARENT {
+            abort!(
+                ident.span(),
+                "expected `element(\"tag\")` or `element(transparent)`"
+            )
+        }
+        Some(attr.span())
+    })?;
+
+    let html_attrs = [
+        ATTRS_ATTR,
+        ATTR_ATTR,
+        ATTR_BOOL_ATTR,
+        CLASS_ATTR,
+        HX_ATTR,
+        HX_SWAP_ATTR,
+        HX_TRIGGER_ATTR,
+        HX_VALS_ATTR,
+        HX_HEADERS_ATTR,
+        FORM_ATTR,
+        BEFORE_ATTR,
+        AFTER_ATTR,
+        ENCODE_ATTR,
+    ];
+
+    for attr in attrs {
+        let Some(id) = attr.path().get_ident() else {
+            continue;
+        };
+        let id = id.to_string();
+        if html_attrs.contains(&id.as_str()) || HTMX_METHODS.contains(&id.as_str()) {
+            abort!(
+                attr.span(),
+                "`element(transparent)` cannot be combined with other element attributes"
+            )
+        }
+    }
+
+    Some(transparent)
+}
+
+/// The condition from `render_if`, or the negated condition from `skip_if`,
+/// with the fields it references resolved, see [expr::resolve_fields].
+fn collect_render_condition(attrs: &[Attribute], markup: &Markup) -> Option<TokenStream> {
+    let mut conditions = attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident(RENDER_IF_ATTR) || attr.path().is_ident(SKIP_IF_ATTR));
+
+    let attr = conditions.next()?;
+
+    if let Some(other) = conditions.next() {
+        abort!(
+            other.span(),
+            "cannot have more than one `render_if` or `skip_if` on element"
+        )
+    }
+
+    let condition = attr.parse_args::<Expr>().unwrap_or_else(|_| {
+        abort!(
+            attr.meta.span(),
+            "expected condition, e.g. `render_if(is_admin)` or `skip_if(items.is_empty())`"
+        )
+    });
+    let condition = expr::resolve_fields(&condition, markup);
+
+    if attr.path().is_ident(SKIP_IF_ATTR) {
+        Some(quote!(!(#condition)))
+    } else {
+        Some(condition)
+    }
+}
+
 fn collect_html_element(attrs: &[Attribute]) -> Option<HtmlElement> {
-    let mut el = None;
+    collect_html_element_or(attrs, None)
+}
+
+/// Same as [collect_html_element], using the `default` element when there is no `element` attribute
+fn collect_html_element_or(attrs: &[Attribute], default: Option<&str>) -> Option<HtmlElement> {
+    let mut el = default.map(ToString::to_string);
     let mut before = vec![];
     let mut after = vec![];
 
@@ -256,13 +1154,60 @@ fn collect_html_element(attrs: &[Attribute]) -> Option<HtmlElement> {
         }
     }
 
-    el.map(|el| HtmlElement { el, before, after })
+    let el = el.map(|el| HtmlElement { el, before, after })?;
+
+    if el.is_void() {
+        for attr in attrs {
+            let Some(id) = attr.meta.path().get_ident() else {
+                continue;
+            };
+
+            if id == FORMAT_ATTR || id == BEFORE_ATTR || id == AFTER_ATTR || id == NEST_ATTR {
+                abort!(
+                    attr.span(),
+                    format!("`{id}` cannot be used on void element `{}`", el.el)
+                )
+            }
+
+            if id == LIST_ATTR && attr.meta.require_path_only().is_err() {
+                abort!(
+                    attr.span(),
+                    format!("`list(nest)` cannot be used on void element `{}`", el.el)
+                )
+            }
+        }
+    }
+
+    Some(el)
+}
+
+/// Abort if any of the fields would render content inside the void element
+fn check_void_content(element: &HtmxStructElement, fields: &syn::Fields) {
+    let Some(ref el) = element.html_element else {
+        return;
+    };
+
+    if !el.is_void() {
+        return;
+    }
+
+    for attr in fields.iter().flat_map(|field| field.attrs.iter()) {
+        if attr.path().is_ident(ELEMENT_ATTR)
+            || attr.path().is_ident(NEST_ATTR)
+            || attr.path().is_ident(LIST_ATTR)
+        {
+            abort!(
+                attr.span(),
+                format!("void element `{}` cannot have any content", el.el)
+            )
+        }
+    }
 }
 
 #[derive(Debug)]
 struct HtmxFieldElement {
     /// Name of the field annotated with `element`
-    field_name: Ident,
+    field_name: Member,
 
     /// The element from the attributes
     html_element: Option<HtmlElement>,
@@ -277,6 +1222,10 @@ struct HtmxFieldElement {
     default: Option<String>,
 
     map: Option<MapExpr>,
+
+    /// Whether to write the content without escaping, obtained from `raw`
+    /// or from the field being a `PreEscaped` type
+    raw: bool,
 }
 
 #[derive(Debug)]
@@ -294,180 +1243,217 @@ impl syn::parse::Parse for MapExpr {
     }
 }
 
+impl HtmlElement {
+    /// Whether the element is one of the [VOID_ELEMENTS]
+    fn is_void(&self) -> bool {
+        VOID_ELEMENTS.contains(&self.el.to_ascii_lowercase().as_str())
+    }
+
+    /// Push the opening tag along with its attributes and the `before` content
+    fn open(&self, attrs: &HtmlAttributes, markup: &mut Markup) {
+        self.open_with(attrs, markup, false)
+    }
+
+    /// Same as [HtmlElement::open], but writes the `hx-swap-oob` attribute
+    /// when rendering the element as an out of band swap
+    fn open_root(&self, attrs: &HtmlAttributes, markup: &mut Markup) {
+        self.open_with(attrs, markup, true)
+    }
+
+    fn open_with(&self, attrs: &HtmlAttributes, markup: &mut Markup, root: bool) {
+        let Self { el, before, .. } = self;
+
+        markup.push_str("<");
+        markup.push_str(el);
+        attrs.write_to(markup);
+        if root {
+            markup.push_oob();
+        }
+        markup.push_str(">");
+
+        for before in before {
+            markup.push_str(before);
+        }
+    }
+
+    /// Push the `after` content and the closing tag.
+    /// Void elements have neither.
+    fn close(&self, markup: &mut Markup) {
+        if self.is_void() {
+            return;
+        }
+
+        let Self { el, after, .. } = self;
+
+        for after in after {
+            markup.push_str(after);
+        }
+
+        markup.push_str("</");
+        markup.push_str(el);
+        markup.push_str(">");
+    }
+}
+
 impl HtmxFieldElement {
-    fn open(&self) -> Option<TokenStream> {
-        let Self {
-            html_element,
-            attrs,
-            ..
-        } = self;
+    /// Push a lookup writing the markup of the field, pushed so far,
+    /// when the `id` attribute of its element matches
+    fn push_lookup(&self, markup: &mut Markup) {
+        if self.html_element.is_none() {
+            return;
+        }
 
-        let (element, before) =
-            html_element
-                .as_ref()
-                .map(|HtmlElement { el, before, .. }| {
-                    let before = before.iter().fold(String::new(), |mut acc, el| {
-                        acc.push_str(el);
-                        acc
-                    });
-                    (quote!(let element = #el;), quote!(let before = #before;))
-                })?;
-
-        let AttributeTokens {
-            static_attrs,
-            request,
-            dyn_attrs,
-            hx_attrs,
-        } = attrs.attr_tokens();
+        let Some(matches) = self.attrs.id_matches(markup) else {
+            return;
+        };
 
-        Some(quote!(
-            {
-                let mut attributes = String::new();
-                #dyn_attrs
-                #static_attrs
-                #hx_attrs
-                #request
-                #element
-                #before
-                let _ = write!(html, r#"<{element}{request}{attributes}>{before}"#);
+        let matches = if self.optional && self.default.is_none() {
+            let access = markup.access(&self.field_name);
+            quote!(#access.is_some() && #matches)
+        } else {
+            matches
+        };
+
+        let tokens = markup.to_tokens();
+        markup.push_lookup(quote!(
+            if #matches {
+                #tokens
+                return Ok(true);
             }
-        ))
+        ));
     }
 
-    fn close(&self) -> Option<TokenStream> {
-        let (element, after) =
-            self.html_element
-                .as_ref()
-                .map(|HtmlElement { el, after, .. }| {
-                    let after = after.iter().fold(String::new(), |mut acc, el| {
-                        acc.push_str(el);
-                        acc
-                    });
-                    (quote!(let element = #el;), quote!(let after = #after;))
-                })?;
-        Some(quote!(
-            {
-                #element
-                #after
-                let _ = write!(html, "{after}</{element}>");
-            }
-        ))
+    fn open(&self, markup: &mut Markup) {
+        if let Some(ref element) = self.html_element {
+            element.open(&self.attrs, markup)
+        }
+    }
+
+    fn close(&self, markup: &mut Markup) {
+        if let Some(ref element) = self.html_element {
+            element.close(markup)
+        }
     }
 
-    fn to_tokens(&self, list: bool) -> TokenStream {
+    /// Push the whole element to the markup
+    fn write_to(&self, markup: &mut Markup, list: bool) {
         let Self {
             field_name,
-            html_element,
             attrs,
             optional,
             default,
             map,
+            raw,
+            ..
         } = self;
 
-        let AttributeTokens {
-            static_attrs,
-            dyn_attrs,
-            hx_attrs,
-            request,
-        } = attrs.attr_tokens();
+        let access = markup.access(field_name);
 
         let _self = if list || *optional && default.is_none() {
             quote!(el)
         } else {
-            quote!(self.#field_name)
+            access.clone()
         };
 
-        let content = attrs
+        let escape = |value: TokenStream| {
+            if *raw {
+                value
+            } else {
+                quote!(htmxpress::escape::Text(&#value))
+            }
+        };
+
+        let value = if let Some(default) = default {
+            escape(quote!(#_self.as_deref().unwrap_or(#default)))
+        } else if let Some(MapExpr { expr, .. }) = map {
+            escape(quote!(#expr))
+        } else {
+            escape(quote!(#_self))
+        };
+
+        let map_var = map
+            .as_ref()
+            .map(|MapExpr { var, .. }| quote!(let #var = &#_self;));
+
+        let (content, static_len, values) = attrs
             .format_str
             .as_ref()
             .map(|FormatParams { fmt, args }| {
+                let values = args.len() + 1;
+                // Other fields are escaped by their own type, not the one of this field
                 let args = args.iter().map(|arg| {
-                    quote!(self.#arg)
-                });
-                
-                if let Some(default) = default {
-                    quote!(
-                        let content = format!(#fmt, #_self.as_deref().unwrap_or(#default), #(#args),*);
-                    )
-                } else if let Some(MapExpr { var, expr }) = map {
-                    quote!(
-                        let #var = &#_self;
-                        let content = format!(#fmt, #expr, #(#args),*);
-                    )
-                } else {
-                    quote!(
-                        let content = format!(#fmt, #_self, #(#args),*);
-                    )
-                }
-            })
-            .unwrap_or_else(|| {
-                if let Some(default) = default {
-                    quote!(let content = format!("{}", #_self.as_deref().unwrap_or(#default));)
-                } else if let Some(MapExpr { var, expr }) = map {
-                    quote!(
-                        let #var = &#_self;
-                        let content = format!("{}", #expr);
-                    )
-                } else {
-                    quote!(let content = format!("{}", #_self);)
-                }
-            });
-
-        let element = html_element
-            .as_ref()
-            .map(|HtmlElement { el, before, after }| {
-                let el_tokens = quote!(let element = #el;);
-                let before = before.iter().fold(String::new(), |mut acc, el| {
-                    acc.push_str(el);
-                    acc
-                });
-                let before_tokens = quote!(
-                    let before = #before;
-                );
-                let after = after.iter().fold(String::new(), |mut acc, el| {
-                    acc.push_str(el);
-                    acc
+                    let access = markup.access(arg);
+                    if markup.is_raw(arg) {
+                        access
+                    } else {
+                        quote!(htmxpress::escape::Text(&#access))
+                    }
                 });
-                let after_tokens = quote!(
-                    let after = #after;
-                );
-                quote!(
-                    #el_tokens
-                    #before_tokens
-                    #after_tokens
+                (
+                    quote!({
+                        #map_var
+                        write!(html, #fmt, #value, #(#args),*)
+                    }),
+                    fmt.value().len(),
+                    values,
+                )
+            })
+            .unwrap_or_else(|| {
+                (
+                    quote!({
+                        #map_var
+                        write!(html, "{}", #value)
+                    }),
+                    0,
+                    1,
                 )
             });
 
-        let mut el = quote!(
-            {
-                let mut attributes = String::new();
-                #dyn_attrs
-                #static_attrs
-                #hx_attrs
-                #request
-                #content
-                #element
-                let _ = write!(html, r#"<{element}{request}{attributes}>{before}{content}{after}</{element}>"#);
+        let void = self.html_element.as_ref().is_some_and(HtmlElement::is_void);
+
+        let write_element = |markup: &mut Markup| {
+            self.open(markup);
+            // Void elements have no content, the field is used only in attributes
+            if !void {
+                markup.push_write(content, static_len, values);
             }
-        );
+            self.close(markup);
+        };
 
-        if *optional && default.is_none() {
-            el = quote!(
-                if let Some(ref el) = self.#field_name {
-                    #el
-                }
-            )
+        if !list && (!*optional || default.is_some()) {
+            write_element(markup);
+            return;
         }
 
+        let mut el = markup.inner();
+        write_element(&mut el);
+        let tokens = el.to_tokens();
+        let size_hint = el.size_hint();
+
         if list {
-            el = quote!(
-                for el in self.#field_name.iter() {
-                    #el
-                }
+            markup.push_block(
+                quote!(
+                    for el in #access.iter() {
+                        #tokens
+                    }
+                ),
+                quote!(#access.iter().count() * (#size_hint)),
+            )
+        } else {
+            markup.push_block(
+                quote!(
+                    if let Some(ref el) = #access {
+                        #tokens
+                    }
+                ),
+                quote!(
+                    match #access {
+                        Some(_) => #size_hint,
+                        None => 0,
+                    }
+                ),
             )
         }
-
-        el
     }
 }
 
@@ -475,9 +1461,10 @@ impl HtmxFieldElement {
 ///
 /// Ignores the `nest` attribute
 fn collect_htmx_field_el(
-    field_name: &Ident,
+    field_name: &Member,
     attrs: &[Attribute],
     optional: bool,
+    raw: bool,
 ) -> HtmxFieldElement {
     let el = collect_html_element(attrs);
     let el_attrs = collect_html_attrs(attrs);
@@ -489,6 +1476,7 @@ fn collect_htmx_field_el(
         optional,
         default: None,
         map: None,
+        raw,
     };
 
     let mut _attrs = attrs
@@ -516,6 +1504,13 @@ fn collect_htmx_field_el(
             element.default = Some(parse_str(attr));
             continue;
         }
+
+        if id == RAW_ATTR {
+            if attr.meta.require_path_only().is_err() {
+                abort!(attr.meta.span(), "`raw` does not take any arguments")
+            }
+            element.raw = true;
+        }
     }
 
     element
@@ -528,70 +1523,32 @@ struct HtmxStructElement {
 }
 
 impl HtmxStructElement {
-    fn open(&self) -> Option<proc_macro2::TokenStream> {
-        let Self {
-            html_element,
-            attrs,
-        } = self;
-
-        let (element, before) =
-            html_element
-                .as_ref()
-                .map(|HtmlElement { el, before, .. }| {
-                    let el_tokens = quote!(let element = #el;);
-                    let before = before.iter().fold(String::new(), |mut acc, el| {
-                        acc.push_str(el);
-                        acc
-                    });
-                    let before_tokens = quote!(
-                        let before = #before;
-                    );
-                    (el_tokens, before_tokens)
-                })?;
+    fn open(&self, markup: &mut Markup) {
+        if let Some(ref element) = self.html_element {
+            element.open(&self.attrs, markup)
+        }
+    }
 
-        let AttributeTokens {
-            static_attrs,
-            request,
-            dyn_attrs,
-            hx_attrs,
-        } = attrs.attr_tokens();
+    /// An expression checking whether the `id` attribute of
+    /// the element equals `id` from the generated code
+    fn id_matches(&self, markup: &Markup) -> Option<TokenStream> {
+        self.html_element.as_ref()?;
+        self.attrs.id_matches(markup)
+    }
 
-        Some(quote!(
-            {
-                let mut attributes = String::new();
-                #dyn_attrs
-                #static_attrs
-                #hx_attrs
-                #request
-                #element
-                #before
-                let _ = write!(html, r#"<{element}{request}{attributes}>{before}"#);
-            }
-        ))
+    /// Open the root element of the struct or variant. Without an element,
+    /// rendering as an out of band swap fails as there is nothing to swap.
+    fn open_root(&self, markup: &mut Markup) {
+        match self.html_element {
+            Some(ref element) => element.open_root(&self.attrs, markup),
+            None => markup.push_oob_unsupported(),
+        }
     }
 
-    fn close(&self) -> Option<TokenStream> {
-        let (element, after) =
-            self.html_element
-                .as_ref()
-                .map(|HtmlElement { el, after, .. }| {
-                    let el_tokens = quote!(let element = #el;);
-                    let after = after.iter().fold(String::new(), |mut acc, el| {
-                        acc.push_str(el);
-                        acc
-                    });
-                    let after_tokens = quote!(
-                        let after = #after;
-                    );
-                    (el_tokens, after_tokens)
-                })?;
-        Some(quote!(
-            {
-                #element
-                #after
-                let _ = write!(html, "{after}</{element}>");
-            }
-        ))
+    fn close(&self, markup: &mut Markup) {
+        if let Some(ref element) = self.html_element {
+            element.close(markup)
+        }
     }
 
     /// Collect all attributes related to HTML
@@ -606,52 +1563,88 @@ impl HtmxStructElement {
             attrs: el_attrs,
         }
     }
+
+    /// Same as [HtmxStructElement::collect_from], with the element defaulting to `form`
+    fn collect_form(attrs: &[Attribute]) -> Self {
+        Self {
+            html_element: collect_html_element_or(attrs, Some("form")),
+            attrs: collect_html_attrs(attrs),
+        }
+    }
 }
 
 #[derive(Debug, Default)]
 struct HtmlAttributes {
-    /// HTML key="value" attributes, along with
-    /// any hx-*="*" attributes other than AJAX
-    attributes: Vec<(String, String)>,
-
-    /// HTML attributes obtained from `attr`
-    dyn_attributes: Vec<DynamicAttr>,
-
-    /// hx-* attributes from `hx`
-    hx_attributes: Vec<(String, String)>,
+    /// HTML attributes in the order they were declared in
+    attributes: Vec<HtmlAttr>,
 
     /// Format string for the inner content.
     format_str: Option<FormatParams>,
+}
+
+/// A single HTML attribute obtained from any of the attribute macros
+#[derive(Debug)]
+struct HtmlAttr {
+    key: String,
+    value: AttrValue,
+
+    /// Span of the macro attribute declaring it, used for duplicate errors
+    span: Span,
+}
 
-    /// hx-method attribute
-    hx_req: Option<HtmxRequest>,
+#[derive(Debug)]
+enum AttrValue {
+    /// Known at compile time, obtained from `attrs`, `hx`, `hx_swap` and `hx_trigger`
+    Static(String),
+
+    /// Formatted from fields, obtained from `attr` and the htmx methods
+    Format { params: FormatParams, encode: bool },
+
+    /// Serialized from fields, obtained from `hx_vals` and `hx_headers`
+    Json(vals::JsonValues),
+
+    /// Written without a value when the condition is true, obtained from `attr_bool`
+    Bool(Expr),
+
+    /// A class obtained from `class`, written only when its condition is true if it has one
+    Class {
+        class: String,
+        condition: Option<Expr>,
+    },
 }
 
 fn collect_html_attrs(attrs: &[Attribute]) -> HtmlAttributes {
     let mut this = HtmlAttributes::default();
 
-    let _attrs = attrs
-        .iter()
-        .filter_map(|a| Some(a.path().get_ident()?.to_string()))
-        .collect::<Vec<_>>();
+    let encode = attrs.iter().any(|attr| attr.path().is_ident(ENCODE_ATTR));
+    let mut has_method = false;
 
     for attr in attrs {
         let Some(id) = attr.meta.path().get_ident() else {
             continue;
         };
 
+        let span = attr.span();
+
         if HTMX_METHODS.contains(&id.to_string().as_str()) {
-            if this.hx_req.is_some() {
+            if has_method {
                 abort!(
                     attr.span(),
                     "cannot have more than one htmx method on element"
                 )
             }
-
-            let encode = _attrs.contains(&ENCODE_ATTR.to_string());
-
-            let hx_req = parse_htmx_request(attr, encode);
-            this.hx_req = Some(hx_req);
+            has_method = true;
+
+            let HtmxRequest {
+                method,
+                params,
+                encode,
+            } = parse_htmx_request(attr, encode);
+            this.push(
+                method.attribute(),
+                AttrValue::Format { params, encode },
+                span,
+            );
             continue;
         }
 
@@ -667,108 +1660,322 @@ fn collect_html_attrs(attrs: &[Attribute]) -> HtmlAttributes {
             continue;
         }
 
+        if id == HX_SWAP_ATTR {
+            let list = attr.meta.require_list().unwrap_or_else(|_| {
+                abort!(
+                    attr.meta.span(),
+                    "expected swap style and modifiers, e.g. `hx_swap(outerHTML swap:1s)`"
+                )
+            });
+            let swap::HxSwap(swap) = list.parse_args().unwrap_or_else(|e| abort!(e.span(), e));
+            this.push("hx-swap", AttrValue::Static(swap), span);
+            continue;
+        }
+
+        if id == HX_TRIGGER_ATTR {
+            let list = attr.meta.require_list().unwrap_or_else(|_| {
+                abort!(
+                    attr.meta.span(),
+                    "expected triggers, e.g. `hx_trigger(keyup changed delay:500ms)`"
+                )
+            });
+            let trigger::HxTrigger(trigger) =
+                list.parse_args().unwrap_or_else(|e| abort!(e.span(), e));
+            this.push("hx-trigger", AttrValue::Static(trigger), span);
+            continue;
+        }
+
+        if id == HX_VALS_ATTR || id == HX_HEADERS_ATTR {
+            let attribute = if id == HX_VALS_ATTR {
+                "hx-vals"
+            } else {
+                "hx-headers"
+            };
+
+            let list = attr.meta.require_list().unwrap_or_else(|_| {
+                abort!(
+                    attr.meta.span(),
+                    "expected fields, e.g. `{}(id, page)` or `{}(serde = field)`",
+                    id,
+                    id
+                )
+            });
+            let values = list.parse_args().unwrap_or_else(|e| abort!(e.span(), e));
+            this.push(attribute, AttrValue::Json(values), span);
+            continue;
+        }
+
         if id == ATTRS_ATTR {
-            let attrs = parse_name_values(attr);
-            this.attributes.extend(attrs);
+            for (key, value) in parse_name_values(attr) {
+                this.push(key, AttrValue::Static(value), span);
+            }
             continue;
         }
 
         if id == ATTR_ATTR {
-            let attr = parse_dyn_attr(attr);
-            this.dyn_attributes.push(attr);
+            let DynamicAttr { key, params } = parse_dyn_attr(attr);
+            let value = AttrValue::Format {
+                params,
+                encode: false,
+            };
+            this.push(key, value, span);
+        }
+
+        if id == ATTR_BOOL_ATTR {
+            let (key, condition) = parse_bool_attr(attr);
+            this.push(key, AttrValue::Bool(condition), span);
+        }
+
+        if id == CLASS_ATTR {
+            for (class, condition) in parse_classes(attr) {
+                this.push("class", AttrValue::Class { class, condition }, span);
+            }
         }
 
         if id == HX_ATTR {
-            let attrs = parse_hx_attrs(attr);
-            this.hx_attributes.extend(attrs)
+            for (key, value) in parse_hx_attrs(attr) {
+                this.push(key, AttrValue::Static(value), span);
+            }
         }
     }
 
+    this.check_duplicates();
+
     this
 }
 
 impl HtmlAttributes {
-    pub fn attr_tokens(&self) -> AttributeTokens {
-        let static_attrs = self
-            .attributes
-            .iter()
-            .map(|(key, val)| {
-                let var = format_ident!("_{key}");
-                quote!(
-                    {
-                        let #var = format!(r#" {}="{}""#, #key, #val);
-                        let _ = write!(attributes, "{}", #var);
-                    }
-                )
-            })
-            .collect();
+    fn push(&mut self, key: impl Into<String>, value: AttrValue, span: Span) {
+        self.attributes.push(HtmlAttr {
+            key: key.into(),
+            value,
+            span,
+        });
+    }
 
-        let dyn_attrs = self
-            .dyn_attributes
-            .iter()
-            .map(|DynamicAttr { key, params }| {
-                let FormatParams { fmt, args } = params;
-                let args = args.iter().map(|field| quote!(self.#field));
-                quote!({
-                    let _attr = format!(#fmt, #(#args),*);
-                    let _attr = format!(r#" {}="{}""#, #key, _attr);
-                    let _ = write!(attributes, "{}", _attr);
-                })
-            })
-            .collect();
+    /// Abort on any attribute declared more than once, except for `class` and `style`
+    /// which get merged when all of their values are strings.
+    fn check_duplicates(&self) {
+        for (i, attr) in self.attributes.iter().enumerate() {
+            let Some(first) = self.attributes[..i].iter().find(|a| a.key == attr.key) else {
+                continue;
+            };
 
-        let hx_attrs = self
-            .hx_attributes
-            .iter()
-            .map(|(key, val)| {
-                let var = Ident::new("hx_attr", Span::call_site());
-                quote!(
-                    {
-                        let #var = format!(r#" {}="{}""#, #key, #val);
-                        let _ = write!(attributes, "{}", #var);
-                    }
+            let mergeable = |attr: &HtmlAttr| match attr.key.as_str() {
+                "class" => matches!(
+                    attr.value,
+                    AttrValue::Static(_) | AttrValue::Format { .. } | AttrValue::Class { .. }
+                ),
+                "style" => matches!(attr.value, AttrValue::Static(_) | AttrValue::Format { .. }),
+                _ => false,
+            };
+
+            if !mergeable(first) || !mergeable(attr) {
+                abort!(
+                    attr.span,
+                    "duplicate attribute `{}`, it is already set on this element",
+                    attr.key;
+                    help = "only `class` and `style` can be set more than once, in which case they are merged"
                 )
-            })
-            .collect();
+            }
+        }
+    }
 
-        let request = self
-            .hx_req
-            .as_ref()
-            .map(|hx_req| {
-                let method = match hx_req.method {
-                    HtmxMethod::Get => "hx-get",
-                    HtmxMethod::Post => "hx-post",
-                    HtmxMethod::Put => "hx-put",
-                    HtmxMethod::Delete => "hx-delete",
-                    HtmxMethod::Patch => "hx-patch",
-                };
-                hx_req.params.to_tokens(method, hx_req.encode)
-            })
-            .unwrap_or(quote!(let request = String::new();));
+    /// An expression checking whether the `id` attribute equals
+    /// `id` from the generated code, if the element has one
+    fn id_matches(&self, markup: &Markup) -> Option<TokenStream> {
+        let attr = self.attributes.iter().find(|attr| attr.key == "id")?;
+
+        let FormatParams { fmt, args } = match attr.value {
+            AttrValue::Static(ref value) => return Some(quote!(id == #value)),
+            AttrValue::Format { ref params, .. } => params,
+            _ => return None,
+        };
 
-        AttributeTokens {
-            static_attrs,
-            dyn_attrs,
-            hx_attrs,
-            request,
+        if args.is_empty() {
+            let value = fmt.value();
+            return Some(quote!(id == #value));
         }
+
+        let args = args.iter().map(|arg| markup.access(arg));
+
+        Some(quote!({
+            let mut matcher = htmxpress::fragment::IdMatcher::new(id);
+            ::core::fmt::Write::write_fmt(&mut matcher, format_args!(#fmt, #(&#args),*)).is_ok()
+                && matcher.matches()
+        }))
     }
-}
 
-/// Tokens used to create HTML element attributes
-#[derive(Debug)]
-struct AttributeTokens {
-    /// Attribute tokens that do not use the struct's fields. Obtained from `attrs`
-    static_attrs: TokenStream,
+    /// Push the attributes to the markup in declaration order. Merged attributes
+    /// are written at the position of their first declaration.
+    pub fn write_to(&self, markup: &mut Markup) {
+        for (i, attr) in self.attributes.iter().enumerate() {
+            let HtmlAttr { key, value, .. } = attr;
+
+            if self.attributes[..i].iter().any(|a| a.key == *key) {
+                continue;
+            }
+
+            let merged = self.attributes[i..]
+                .iter()
+                .filter(|a| a.key == *key)
+                .map(|a| &a.value)
+                .collect::<Vec<_>>();
+
+            if merged.len() > 1 || matches!(value, AttrValue::Class { .. }) {
+                Self::write_merged(markup, key, &merged);
+                continue;
+            }
+
+            match value {
+                AttrValue::Static(value) => {
+                    markup.push_str(&format!(r#" {key}="{}""#, escape_attr(value)));
+                }
+                AttrValue::Format { params, encode } => params.write_to(markup, key, *encode),
+                AttrValue::Json(values) => values.write_to(markup, key),
+                AttrValue::Bool(condition) => {
+                    let condition = expr::resolve_fields(condition, markup);
+                    let mut attr = markup.inner();
+                    attr.push_str(&format!(" {key}"));
+                    let tokens = attr.to_tokens();
+                    markup.push_block(
+                        quote!(
+                            if #condition {
+                                #tokens
+                            }
+                        ),
+                        quote!(0),
+                    );
+                }
+                AttrValue::Class { .. } => unreachable!(),
+            }
+        }
+    }
+
+    /// Push an attribute declared more than once, i.e. `class` and `style`.
+    ///
+    /// Classes are separated by spaces and styles by semicolons. Conditional classes
+    /// from `class` come after the unconditional ones.
+    fn write_merged(markup: &mut Markup, key: &str, values: &[&AttrValue]) {
+        let is_conditional = |value: &AttrValue| {
+            matches!(
+                value,
+                AttrValue::Class {
+                    condition: Some(_),
+                    ..
+                }
+            )
+        };
+
+        if values.iter().all(|value| is_conditional(value)) {
+            Self::write_classes(markup, values);
+            return;
+        }
+
+        let separator = if key == "style" { "; " } else { " " };
+
+        markup.push_str(&format!(r#" {key}=""#));
+
+        let mut first = true;
+        for value in values.iter().filter(|value| !is_conditional(value)) {
+            let value = match value {
+                AttrValue::Static(value) if key == "style" => value.trim().trim_end_matches(';'),
+                AttrValue::Static(value) | AttrValue::Class { class: value, .. } => value.trim(),
+                AttrValue::Format { params, encode } => {
+                    if !first {
+                        markup.push_str(separator);
+                    }
+                    if key == "style" {
+                        let fmt = params.fmt.value();
+                        let params = FormatParams {
+                            fmt: LitStr::new(fmt.trim().trim_end_matches(';'), params.fmt.span()),
+                            args: params.args.clone(),
+                        };
+                        params.write_value(markup, *encode);
+                    } else {
+                        params.write_value(markup, *encode);
+                    }
+                    first = false;
+                    continue;
+                }
+                _ => unreachable!(),
+            };
+
+            if value.is_empty() {
+                continue;
+            }
+            if !first {
+                markup.push_str(separator);
+            }
+            markup.push_str(&escape_attr(value));
+            first = false;
+        }
+
+        for value in values.iter().filter(|value| is_conditional(value)) {
+            let AttrValue::Class {
+                class,
+                condition: Some(condition),
+            } = value
+            else {
+                unreachable!()
+            };
 
-    /// Attribute tokens that use struct fields. Obtained from `attr`
-    dyn_attrs: TokenStream,
+            let condition = expr::resolve_fields(condition, markup);
+            let mut attr = markup.inner();
+            attr.push_str(&format!(" {}", escape_attr(class)));
+            let tokens = attr.to_tokens();
+            let size_hint = attr.size_hint();
+            markup.push_block(
+                quote!(
+                    if #condition {
+                        #tokens
+                    }
+                ),
+                size_hint,
+            );
+        }
 
-    /// Attribute tokens obtained from `hx`
-    hx_attrs: TokenStream,
+        markup.push_str(r#"""#);
+    }
 
-    /// The hx-method tokens
-    request: TokenStream,
+    /// Push the `class` attribute containing the classes from `class` whose conditions
+    /// are true. The attribute is omitted when there are no classes to write.
+    fn write_classes(markup: &mut Markup, values: &[&AttrValue]) {
+        let classes = values
+            .iter()
+            .map(|value| {
+                let AttrValue::Class { class, condition } = value else {
+                    unreachable!()
+                };
+                let condition = condition
+                    .as_ref()
+                    .map(|condition| expr::resolve_fields(condition, markup))
+                    .unwrap_or_else(|| quote!(true));
+                quote!((#class, #condition))
+            })
+            .collect::<Vec<_>>();
+
+        let mut attr = markup.inner();
+        attr.push_str(r#" class=""#);
+        attr.push_write(
+            quote!(write!(html, "{}", htmxpress::escape::ClassList(&classes))),
+            0,
+            1,
+        );
+        attr.push_str(r#"""#);
+        let tokens = attr.to_tokens();
+        let size_hint = attr.size_hint();
+
+        markup.push_block(
+            quote!({
+                let classes = [#(#classes),*];
+                if classes.iter().any(|(_, enabled)| *enabled) {
+                    #tokens
+                }
+            }),
+            size_hint,
+        );
+    }
 }
 
 fn parse_expr(attr: &Attribute) -> MapExpr {
@@ -928,6 +2135,70 @@ fn parse_dyn_attr(attr: &Attribute) -> DynamicAttr {
     .unwrap_or_else(|e| abort!(list.span(), &format!("{e}")))
 }
 
+fn parse_classes(attr: &Attribute) -> Vec<(String, Option<Expr>)> {
+    let list = attr.meta.require_list().unwrap_or_else(|_| {
+        abort!(
+            attr.meta.span(),
+            r#"expected classes, e.g. `class("active" if selected, "item")`"#
+        )
+    });
+
+    list.parse_args_with(|input: ParseStream| {
+        let mut classes = vec![];
+
+        while !input.is_empty() {
+            let class = input.parse::<LitStr>()?;
+
+            let condition = if input.peek(Token![if]) {
+                input.parse::<Token![if]>()?;
+                Some(input.parse::<Expr>()?)
+            } else {
+                None
+            };
+
+            classes.push((class.value(), condition));
+
+            if input.is_empty() {
+                break;
+            }
+
+            input.parse::<Token![,]>()?;
+        }
+
+        Ok(classes)
+    })
+    .unwrap_or_else(|e| {
+        abort!(
+            e.span(),
+            format!(r#"{e}, expected classes, e.g. `class("active" if selected, "item")`"#)
+        )
+    })
+}
+
+fn parse_bool_attr(attr: &Attribute) -> (String, Expr) {
+    let list = attr.meta.require_list().unwrap_or_else(|_| {
+        abort!(
+            attr.meta.span(),
+            r#"expected attribute and condition, e.g. `attr_bool("disabled", is_locked)`"#
+        )
+    });
+
+    list.parse_args_with(|input: ParseStream| {
+        let key = input.parse::<LitStr>()?;
+        input.parse::<Token![,]>()?;
+        let condition = input.parse::<Expr>()?;
+        Ok((key.value(), condition))
+    })
+    .unwrap_or_else(|e| {
+        abort!(
+            e.span(),
+            format!(
+                r#"{e}, expected attribute and condition, e.g. `attr_bool("disabled", is_locked)`"#
+            )
+        )
+    })
+}
+
 fn extract_list_and_args(attr: &Attribute) -> (&MetaList, &Ident) {
     let list = attr
         .meta
@@ -966,6 +2237,18 @@ enum HtmxMethod {
     Patch,
 }
 
+impl HtmxMethod {
+    fn attribute(&self) -> &'static str {
+        match self {
+            HtmxMethod::Get => "hx-get",
+            HtmxMethod::Post => "hx-post",
+            HtmxMethod::Put => "hx-put",
+            HtmxMethod::Delete => "hx-delete",
+            HtmxMethod::Patch => "hx-patch",
+        }
+    }
+}
+
 /// Parameters for format strings for attributes
 /// such as `hx_get("/{}", foo)`. Also used when
 /// there are no substitutions.
@@ -975,24 +2258,33 @@ struct FormatParams {
     /// could just be a raw string without any substitutions.
     fmt: LitStr,
 
-    /// Optional args for the fmt used for self fields.
-    args: Vec<Ident>,
+    /// Optional args for the fmt used for self fields. Either field
+    /// names or indices when the fields are unnamed.
+    args: Vec<Member>,
 }
 
 impl FormatParams {
-    /// Create the tokens for this struct which format a string based
-    /// on its args.
+    /// Push the attribute formatted from this struct to the markup.
+    ///
+    /// The string written will be:
     ///
-    /// The resulting string (the one created by the tokens) will be:
+    /// ` attribute="format!(self.fmt, self.args)"`
     ///
-    /// ` attribute=format!(self.fmt, self.args)`
-    fn to_tokens(&self, attribute: &str, encode: bool) -> TokenStream {
+    /// When there are no args, the attribute is known at compile time.
+    fn write_to(&self, markup: &mut Markup, attribute: &str, encode: bool) {
+        // Write the key and the quotes as static markup
+        // so they get merged with the surrounding markup
+        markup.push_str(&format!(r#" {attribute}=""#));
+        self.write_value(markup, encode);
+        markup.push_str(r#"""#);
+    }
+
+    /// Push only the formatted value to the markup, without the attribute key and quotes.
+    fn write_value(&self, markup: &mut Markup, encode: bool) {
         let fmt = &self.fmt;
         let args = &self.args;
 
         if args.is_empty() {
-            let value = fmt.value();
-
             // Abort if encode and no args
             if encode {
                 abort!(
@@ -1001,24 +2293,26 @@ impl FormatParams {
                 )
             }
 
-            quote!(
-                let attribute = #attribute;
-                let path = format!(r#" {attribute}="{}""#, #value);
-                let request = path;
-            )
+            markup.push_str(&escape_attr(&fmt.value()));
         } else {
-            let args = args.iter().map(|field| {
-                if encode {
-                    quote!(htmxpress::urlencoding::encode(&self.#field))
-                } else {
-                    quote!(self.#field)
-                }
-            });
-            let path = format!(r#" {attribute}="{}""#, fmt.value());
+            let values = args.len();
+            let args = args
+                .iter()
+                .map(|field| {
+                    let field = markup.access(field);
+                    if encode {
+                        quote!(htmxpress::escape::Attr(htmxpress::urlencoding::encode(&#field)))
+                    } else {
+                        quote!(htmxpress::escape::Attr(&#field))
+                    }
+                })
+                .collect::<Vec<_>>();
 
-            quote!(
-                let request = format!(#path, #(#args),*);
-            )
+            markup.push_write(
+                quote!(write!(html, #fmt, #(#args),*)),
+                fmt.value().len(),
+                values,
+            );
         }
     }
 }
@@ -1051,3 +2345,68 @@ impl syn::parse::Parse for FormatParams {
         Ok(Self { fmt, args })
     }
 }
+
+/// Check whether the type is one of the [PRE_ESCAPED_TYPES], either directly or as the
+/// item of one of the [PRE_ESCAPED_CONTAINERS], a slice, an array or a reference.
+///
+/// Any other generic type is escaped, as its `Display` could write anything.
+fn is_pre_escaped(ty: &syn::Type) -> bool {
+    match ty {
+        syn::Type::Path(p) => {
+            let Some(seg) = p.path.segments.last() else {
+                return false;
+            };
+
+            let ident = seg.ident.to_string();
+
+            if p.qself.is_none() && PRE_ESCAPED_TYPES.contains(&ident.as_str()) {
+                let len = p.path.segments.len();
+                let module: Vec<String> = p
+                    .path
+                    .segments
+                    .iter()
+                    .take(len - 1)
+                    .map(|seg| seg.ident.to_string())
+                    .collect();
+
+                return module.is_empty()
+                    || PRE_ESCAPED_MODULES
+                        .iter()
+                        .any(|m| m.iter().copied().eq(module.iter().map(String::as_str)));
+            }
+
+            if !PRE_ESCAPED_CONTAINERS.contains(&ident.as_str()) {
+                return false;
+            }
+
+            let syn::PathArguments::AngleBracketed(ref args) = seg.arguments else {
+                return false;
+            };
+
+            match args.args.first() {
+                Some(syn::GenericArgument::Type(ty)) if args.args.len() == 1 => is_pre_escaped(ty),
+                _ => false,
+            }
+        }
+        syn::Type::Reference(r) => is_pre_escaped(&r.elem),
+        syn::Type::Slice(s) => is_pre_escaped(&s.elem),
+        syn::Type::Array(a) => is_pre_escaped(&a.elem),
+        _ => false,
+    }
+}
+
+/// Escape a value known at compile time so it can be placed in a quoted attribute
+fn escape_attr(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for ch in value.chars() {
+        match ch {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            ch => escaped.push(ch),
+        }
+    }
+    escaped
+}
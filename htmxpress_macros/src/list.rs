@@ -0,0 +1,179 @@
+//! Parsing of `#[list(map, ...)]`, e.g. `#[list(map, key = "th", value = "td", entry = "tr")]`,
+//! rendering collections of key value pairs such as maps.
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{parse::ParseStream, GenericArgument, Ident, LitStr, PathArguments, Token, Type};
+
+use crate::markup::Markup;
+
+/// The elements and formats each key value pair is written with
+#[derive(Debug)]
+pub struct MapList {
+    /// Element of the key, `dt` by default
+    key: String,
+
+    /// Element of the value, `dd` by default
+    value: String,
+
+    /// Element wrapping both the key and the value, if any
+    entry: Option<String>,
+
+    /// Format of the key's content, which can reference both `{key}` and `{value}`
+    key_format: Option<LitStr>,
+
+    /// Format of the value's content, which can reference both `{key}` and `{value}`
+    value_format: Option<LitStr>,
+}
+
+impl MapList {
+    /// Push a loop writing every pair of the collection accessed with `access`,
+    /// escaping the keys unless `key_raw` is set and the values unless `value_raw` is set
+    pub fn write_to(
+        &self,
+        markup: &mut Markup,
+        access: &TokenStream,
+        key_raw: bool,
+        value_raw: bool,
+    ) {
+        let mut entry = markup.inner();
+
+        if let Some(ref el) = self.entry {
+            entry.push_str(&format!("<{el}>"));
+        }
+        write_item(&mut entry, &self.key, self.key_format.as_ref(), quote!(key));
+        write_item(
+            &mut entry,
+            &self.value,
+            self.value_format.as_ref(),
+            quote!(value),
+        );
+        if let Some(ref el) = self.entry {
+            entry.push_str(&format!("</{el}>"));
+        }
+
+        let tokens = entry.to_tokens();
+        let size_hint = entry.size_hint();
+
+        let key = if key_raw {
+            quote!(key)
+        } else {
+            quote!(htmxpress::escape::Text(key))
+        };
+        let value = if value_raw {
+            quote!(value)
+        } else {
+            quote!(htmxpress::escape::Text(value))
+        };
+
+        markup.push_block(
+            quote!(
+                for (key, value) in #access.iter() {
+                    #[allow(unused_variables)]
+                    let (key, value) = (#key, #value);
+                    #tokens
+                }
+            ),
+            quote!(#access.iter().count() * (#size_hint)),
+        );
+    }
+}
+
+/// The types of the keys and values of a map, e.g. `BTreeMap<K, V>`,
+/// or of a list of pairs, e.g. `Vec<(K, V)>`
+pub fn pair_types(ty: &Type) -> Option<(&Type, &Type)> {
+    match ty {
+        Type::Path(p) => {
+            let seg = p.path.segments.last()?;
+            let PathArguments::AngleBracketed(ref args) = seg.arguments else {
+                return None;
+            };
+
+            let mut types = args.args.iter().filter_map(|arg| match arg {
+                GenericArgument::Type(ty) => Some(ty),
+                _ => None,
+            });
+
+            match (types.next()?, types.next()) {
+                (key, Some(value)) => Some((key, value)),
+                (item, None) => pair_types(item),
+            }
+        }
+        Type::Tuple(t) if t.elems.len() == 2 => Some((&t.elems[0], &t.elems[1])),
+        Type::Reference(r) => pair_types(&r.elem),
+        Type::Slice(s) => pair_types(&s.elem),
+        Type::Array(a) => pair_types(&a.elem),
+        _ => None,
+    }
+}
+
+/// Push the element of the key or the value, with the content written from
+/// the format if there is one, or from `var` otherwise
+fn write_item(markup: &mut Markup, el: &str, format: Option<&LitStr>, var: TokenStream) {
+    markup.push_str(&format!("<{el}>"));
+    match format {
+        Some(fmt) => markup.push_write(quote!(write!(html, #fmt)), fmt.value().len(), 1),
+        None => markup.push_write(quote!(write!(html, "{}", #var)), 0, 1),
+    }
+    markup.push_str(&format!("</{el}>"));
+}
+
+impl syn::parse::Parse for MapList {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let map = input.parse::<Ident>()?;
+        if map != "map" {
+            return Err(syn::Error::new(
+                map.span(),
+                "expected `map`, e.g. `list(map, key = \"dt\", value = \"dd\")`",
+            ));
+        }
+
+        let mut this = Self {
+            key: "dt".to_string(),
+            value: "dd".to_string(),
+            entry: None,
+            key_format: None,
+            value_format: None,
+        };
+
+        let mut seen: Vec<String> = vec![];
+
+        while !input.is_empty() {
+            input.parse::<Token![,]>()?;
+
+            if input.is_empty() {
+                break;
+            }
+
+            let option = input.parse::<Ident>()?;
+            input.parse::<Token![=]>()?;
+            let lit = input.parse::<LitStr>()?;
+
+            let name = option.to_string();
+            if seen.contains(&name) {
+                return Err(syn::Error::new(
+                    option.span(),
+                    format!("duplicate option `{name}`"),
+                ));
+            }
+
+            match name.as_str() {
+                "key" => this.key = lit.value(),
+                "value" => this.value = lit.value(),
+                "entry" => this.entry = Some(lit.value()),
+                "key_format" => this.key_format = Some(lit),
+                "value_format" => this.value_format = Some(lit),
+                _ => {
+                    return Err(syn::Error::new(
+                        option.span(),
+                        "expected one of `key`, `value`, `entry`, `key_format` or `value_format`",
+                    ))
+                }
+            }
+
+            seen.push(name);
+        }
+
+        Ok(this)
+    }
+}
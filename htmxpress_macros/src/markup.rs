@@ -0,0 +1,332 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::Member;
+
+/// Estimated length of a single value written at runtime, used for size hints
+const DYNAMIC_SIZE_HINT: usize = 16;
+
+/// The markup an element renders, split into fragments known at
+/// compile time and fragments that can only be written at runtime.
+///
+/// Adjacent static fragments are merged into a single string literal
+/// when the tokens are created.
+#[derive(Debug)]
+pub struct Markup {
+    /// Name of the struct the markup belongs to, used for errors
+    strct: String,
+
+    /// The field the markup currently being pushed belongs to, used for errors
+    field: Option<String>,
+
+    /// How the fields are accessed in the generated code
+    receiver: Receiver,
+
+    /// Fields written without escaping, see [Markup::is_raw]
+    raw_fields: Vec<Member>,
+
+    fragments: Vec<Fragment>,
+
+    /// Statements searching for a fragment by its `id`, see [Markup::push_lookup]
+    lookups: Vec<TokenStream>,
+
+    /// Statements adding `<head>` items to `head` in the generated code
+    heads: Vec<TokenStream>,
+}
+
+/// How the fields of an element are accessed in the generated code
+#[derive(Debug, Clone, Copy)]
+pub enum Receiver {
+    /// Fields are accessed through `self`, i.e. `self.foo`
+    SelfRef,
+
+    /// Fields are bound to references by a match pattern, i.e.
+    /// `Self::Variant { foo: __foo }`. See [binding].
+    Bindings,
+}
+
+/// The identifier a field is bound to when matching on enum variants
+pub fn binding(member: &Member) -> syn::Ident {
+    match member {
+        Member::Named(ident) => format_ident!("__{ident}"),
+        Member::Unnamed(index) => format_ident!("__{}", index.index),
+    }
+}
+
+/// The name of the field as written in the source
+pub fn member_name(member: &Member) -> String {
+    match member {
+        Member::Named(ident) => ident.to_string(),
+        Member::Unnamed(index) => index.index.to_string(),
+    }
+}
+
+#[derive(Debug)]
+enum Fragment {
+    /// HTML known at compile time
+    Static { html: String, field: Option<String> },
+
+    /// An expression writing to `html`, evaluating to `core::fmt::Result`
+    Write {
+        tokens: TokenStream,
+        field: Option<String>,
+        size_hint: usize,
+    },
+
+    /// Statements writing to `html` which handle their own errors
+    Block {
+        tokens: TokenStream,
+        size_hint: TokenStream,
+    },
+}
+
+impl Markup {
+    pub fn new(strct: &str, receiver: Receiver) -> Self {
+        Self {
+            strct: strct.to_string(),
+            field: None,
+            receiver,
+            raw_fields: vec![],
+            fragments: vec![],
+            lookups: vec![],
+            heads: vec![],
+        }
+    }
+
+    /// Create empty markup belonging to the same struct, used for
+    /// markup written inside blocks such as loops
+    pub fn inner(&self) -> Self {
+        Self {
+            strct: self.strct.clone(),
+            field: self.field.clone(),
+            receiver: self.receiver,
+            raw_fields: self.raw_fields.clone(),
+            fragments: vec![],
+            lookups: vec![],
+            heads: vec![],
+        }
+    }
+
+    /// Name of the struct the markup belongs to
+    pub fn strct(&self) -> String {
+        self.strct.clone()
+    }
+
+    /// Attribute any following fragments to the given field
+    pub fn set_field(&mut self, field: Option<&Member>) {
+        self.field = field.map(member_name);
+    }
+
+    /// Set the fields written without escaping, i.e. the pre-escaped ones and those with `raw`
+    pub fn set_raw_fields(&mut self, fields: Vec<Member>) {
+        self.raw_fields = fields;
+    }
+
+    /// Whether the field is written without escaping, used for fields
+    /// referenced in the format string of another field
+    pub fn is_raw(&self, member: &Member) -> bool {
+        self.raw_fields.contains(member)
+    }
+
+    /// A place expression for the given field
+    pub fn access(&self, member: &Member) -> TokenStream {
+        match self.receiver {
+            Receiver::SelfRef => quote!(self.#member),
+            Receiver::Bindings => {
+                let binding = binding(member);
+                quote!((*#binding))
+            }
+        }
+    }
+
+    /// Push HTML known at compile time, merging it with
+    /// the previous fragment if that one is static too
+    pub fn push_str(&mut self, html: &str) {
+        if html.is_empty() {
+            return;
+        }
+
+        if let Some(Fragment::Static { html: last, .. }) = self.fragments.last_mut() {
+            last.push_str(html);
+            return;
+        }
+
+        self.fragments.push(Fragment::Static {
+            html: html.to_string(),
+            field: self.field.clone(),
+        })
+    }
+
+    /// Push an expression writing to `html` and evaluating to `core::fmt::Result`,
+    /// e.g. `write!(html, "{}", self.foo)`.
+    ///
+    /// `static_len` is the length of the known parts of the output and `values`
+    /// the amount of values interpolated at runtime.
+    pub fn push_write(&mut self, tokens: TokenStream, static_len: usize, values: usize) {
+        self.fragments.push(Fragment::Write {
+            tokens,
+            field: self.field.clone(),
+            size_hint: static_len + values * DYNAMIC_SIZE_HINT,
+        })
+    }
+
+    /// Push statements writing to `html`. The statements must propagate
+    /// a `RenderError` on failure.
+    ///
+    /// `size_hint` is an expression estimating the length of the output.
+    pub fn push_block(&mut self, tokens: TokenStream, size_hint: TokenStream) {
+        self.fragments.push(Fragment::Block { tokens, size_hint })
+    }
+
+    /// Push statements used when searching for a fragment. The statements
+    /// must write the fragment to `html` and return `Ok(true)` if the element
+    /// they check has the `id` from the generated code.
+    pub fn push_lookup(&mut self, tokens: TokenStream) {
+        self.lookups.push(tokens)
+    }
+
+    /// Statements searching for the fragment, see [Markup::push_lookup]
+    pub fn lookups(&self) -> TokenStream {
+        self.lookups.iter().cloned().collect()
+    }
+
+    pub fn has_lookups(&self) -> bool {
+        !self.lookups.is_empty()
+    }
+
+    /// Push statements adding `<head>` items to `head` in the generated code
+    pub fn push_head(&mut self, tokens: TokenStream) {
+        self.heads.push(tokens)
+    }
+
+    /// Statements collecting the `<head>` items, see [Markup::push_head]
+    pub fn heads(&self) -> TokenStream {
+        self.heads.iter().cloned().collect()
+    }
+
+    pub fn has_heads(&self) -> bool {
+        !self.heads.is_empty()
+    }
+
+    /// Push the fragments, lookups and heads of the other markup
+    pub fn append(&mut self, other: Markup) {
+        for fragment in other.fragments {
+            if let (Fragment::Static { html, .. }, Some(Fragment::Static { html: last, .. })) =
+                (&fragment, self.fragments.last_mut())
+            {
+                last.push_str(html);
+                continue;
+            }
+            self.fragments.push(fragment);
+        }
+        self.lookups.extend(other.lookups);
+        self.heads.extend(other.heads);
+    }
+
+    /// Make the whole markup conditional, writing it and searching it for fragments
+    /// and `<head>` items only when `condition` evaluates to true in the generated code
+    pub fn render_if(&mut self, condition: TokenStream) {
+        if !self.fragments.is_empty() {
+            let tokens = self.to_tokens();
+            let size_hint = self.size_hint();
+            self.fragments = vec![Fragment::Block {
+                tokens: quote!(
+                    if #condition {
+                        #tokens
+                    }
+                ),
+                size_hint: quote!(if #condition { #size_hint } else { 0 }),
+            }];
+        }
+
+        if self.has_lookups() {
+            let lookups = self.lookups();
+            self.lookups = vec![quote!(
+                if #condition {
+                    #lookups
+                }
+            )];
+        }
+
+        if self.has_heads() {
+            let heads = self.heads();
+            self.heads = vec![quote!(
+                if #condition {
+                    #heads
+                }
+            )];
+        }
+    }
+
+    /// Push the `hx-swap-oob` attribute of the root element, written
+    /// only when `oob` is set in the generated code
+    pub fn push_oob(&mut self) {
+        let error = self.error(&None);
+        self.push_block(
+            quote!(
+                if let Some(oob) = oob {
+                    write!(html, " hx-swap-oob=\"{}\"", htmxpress::escape::Attr(oob))
+                        .map_err(|_| #error)?;
+                }
+            ),
+            quote!(0),
+        )
+    }
+
+    /// Push a check failing the render when `oob` is set in the generated code,
+    /// used when there is no root element to write `hx-swap-oob` to
+    pub fn push_oob_unsupported(&mut self) {
+        let error = self.error(&None);
+        self.push_block(
+            quote!(
+                if oob.is_some() {
+                    return Err(#error);
+                }
+            ),
+            quote!(0),
+        )
+    }
+
+    fn error(&self, field: &Option<String>) -> TokenStream {
+        let strct = &self.strct;
+        match field {
+            Some(field) => quote!(htmxpress::RenderError::new(#strct, Some(#field))),
+            None => quote!(htmxpress::RenderError::new(#strct, None)),
+        }
+    }
+
+    /// Statements writing the markup to `html`
+    pub fn to_tokens(&self) -> TokenStream {
+        let error = |field: &Option<String>| self.error(field);
+
+        self.fragments
+            .iter()
+            .map(|fragment| match fragment {
+                Fragment::Static { html, field } => {
+                    let error = error(field);
+                    quote!(html.write_str(#html).map_err(|_| #error)?;)
+                }
+                Fragment::Write { tokens, field, .. } => {
+                    let error = error(field);
+                    quote!((#tokens).map_err(|_| #error)?;)
+                }
+                Fragment::Block { tokens, .. } => tokens.clone(),
+            })
+            .collect()
+    }
+
+    /// An expression estimating the length of the rendered markup
+    pub fn size_hint(&self) -> TokenStream {
+        let mut known = 0;
+        let mut dynamic = vec![];
+
+        for fragment in self.fragments.iter() {
+            match fragment {
+                Fragment::Static { html, .. } => known += html.len(),
+                Fragment::Write { size_hint, .. } => known += size_hint,
+                Fragment::Block { size_hint, .. } => dynamic.push(size_hint),
+            }
+        }
+
+        quote!(#known #(+ #dynamic)*)
+    }
+}
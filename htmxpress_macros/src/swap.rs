@@ -0,0 +1,171 @@
+//! Parsing of `#[hx_swap(...)]`, e.g.
+//! `#[hx_swap(outerHTML swap:1s show:"#item":top transition:true)]`.
+//!
+//! The syntax follows the one used by htmx for `hx-swap`, with CSS selectors
+//! written as string literals. The output is the validated `hx-swap` value.
+
+use proc_macro2::Span;
+use syn::{parse::ParseStream, Ident, Lit, LitBool, LitStr, Token};
+
+const STYLES: [&str; 9] = [
+    "innerHTML",
+    "outerHTML",
+    "textContent",
+    "beforebegin",
+    "afterbegin",
+    "beforeend",
+    "afterend",
+    "delete",
+    "none",
+];
+
+const MODIFIERS: [&str; 7] = [
+    "swap",
+    "settle",
+    "scroll",
+    "show",
+    "focus-scroll",
+    "transition",
+    "ignoreTitle",
+];
+
+/// A validated `hx-swap` value
+#[derive(Debug)]
+pub struct HxSwap(pub String);
+
+impl syn::parse::Parse for HxSwap {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let style = input.parse::<Ident>()?;
+        let mut value = parse_style(&style)?.to_string();
+
+        let mut seen: Vec<String> = vec![];
+
+        while !input.is_empty() {
+            let (modifier, span) = parse_modifier(input)?;
+
+            if seen.contains(&modifier) {
+                return Err(syn::Error::new(
+                    span,
+                    format!("duplicate swap modifier `{modifier}`"),
+                ));
+            }
+
+            input.parse::<Token![:]>()?;
+
+            let modifier_value = match modifier.as_str() {
+                "swap" | "settle" => parse_timing(input)?,
+                "scroll" => parse_scroll(input, false)?,
+                "show" => parse_scroll(input, true)?,
+                _ => input.parse::<LitBool>()?.value.to_string(),
+            };
+
+            value.push_str(&format!(" {modifier}:{modifier_value}"));
+            seen.push(modifier);
+        }
+
+        Ok(Self(value))
+    }
+}
+
+fn parse_style(style: &Ident) -> syn::Result<&'static str> {
+    let name = style.to_string();
+
+    if let Some(style) = STYLES.iter().find(|s| **s == name) {
+        return Ok(style);
+    }
+
+    let message = match STYLES.iter().find(|s| s.eq_ignore_ascii_case(&name)) {
+        Some(style) => format!("unknown swap style `{name}`, did you mean `{style}`?"),
+        None => format!(
+            "unknown swap style `{name}`, expected one of {}",
+            STYLES.join(", ")
+        ),
+    };
+
+    Err(syn::Error::new(style.span(), message))
+}
+
+/// Parse the name of a modifier, joining `focus-scroll`
+fn parse_modifier(input: ParseStream) -> syn::Result<(String, Span)> {
+    let ident = input.parse::<Ident>()?;
+    let mut modifier = ident.to_string();
+
+    if input.peek(Token![-]) {
+        input.parse::<Token![-]>()?;
+        let rest = input.parse::<Ident>()?;
+        modifier = format!("{modifier}-{rest}");
+    }
+
+    if !MODIFIERS.contains(&modifier.as_str()) {
+        let message = match MODIFIERS.iter().find(|m| m.eq_ignore_ascii_case(&modifier)) {
+            Some(m) => format!("unknown swap modifier `{modifier}`, did you mean `{m}`?"),
+            None => format!(
+                "unknown swap modifier `{modifier}`, expected one of {}",
+                MODIFIERS.join(", ")
+            ),
+        };
+        return Err(syn::Error::new(ident.span(), message));
+    }
+
+    Ok((modifier, ident.span()))
+}
+
+/// Parse a htmx timing such as `1s`, `1.5s`, `100ms` or `100`
+pub fn parse_timing(input: ParseStream) -> syn::Result<String> {
+    let lit = input.parse::<Lit>()?;
+
+    let (digits, suffix) = match lit {
+        Lit::Int(ref int) => (int.base10_digits().to_string(), int.suffix().to_string()),
+        Lit::Float(ref float) => (
+            float.base10_digits().to_string(),
+            float.suffix().to_string(),
+        ),
+        _ => {
+            return Err(syn::Error::new(
+                lit.span(),
+                "expected a timing, e.g. `1s` or `100ms`",
+            ))
+        }
+    };
+
+    if !["", "s", "ms"].contains(&suffix.as_str()) {
+        return Err(syn::Error::new(
+            lit.span(),
+            format!("invalid timing unit `{suffix}`, expected `s` or `ms`"),
+        ));
+    }
+
+    Ok(format!("{digits}{suffix}"))
+}
+
+/// Parse the value of `scroll` or `show`, e.g. `top`, `window:bottom` or `"#item":top`
+fn parse_scroll(input: ParseStream, allow_none: bool) -> syn::Result<String> {
+    let target = if input.peek(LitStr) {
+        Some(input.parse::<LitStr>()?.value())
+    } else {
+        let ident = input.parse::<Ident>()?;
+        match ident.to_string().as_str() {
+            "top" | "bottom" => return Ok(ident.to_string()),
+            "none" if allow_none => return Ok(ident.to_string()),
+            "window" => Some(ident.to_string()),
+            _ => {
+                return Err(syn::Error::new(
+                    ident.span(),
+                    r##"expected `top`, `bottom`, `window:top`, or a selector, e.g. `"#item":top`"##,
+                ))
+            }
+        }
+    };
+
+    input.parse::<Token![:]>()?;
+    let position = input.parse::<Ident>()?;
+
+    if position != "top" && position != "bottom" {
+        return Err(syn::Error::new(
+            position.span(),
+            "expected `top` or `bottom`",
+        ));
+    }
+
+    Ok(format!("{}:{position}", target.unwrap_or_default()))
+}
@@ -0,0 +1,258 @@
+//! Parsing of `#[hx_trigger(...)]`, e.g.
+//! `#[hx_trigger(keyup[ctrlKey] changed delay:500ms from:"#search", load)]`.
+//!
+//! The syntax follows the one used by htmx for `hx-trigger`, with CSS selectors written
+//! as string literals. Event names and filters can be string literals as well, for the ones
+//! that are not valid Rust tokens. The output is the validated `hx-trigger` value.
+
+use syn::{parse::ParseStream, Ident, Lit, LitStr, Token};
+
+use crate::swap::parse_timing;
+
+const MODIFIERS: [&str; 10] = [
+    "once",
+    "changed",
+    "delay",
+    "throttle",
+    "from",
+    "target",
+    "consume",
+    "queue",
+    "root",
+    "threshold",
+];
+
+/// A validated `hx-trigger` value
+#[derive(Debug)]
+pub struct HxTrigger(pub String);
+
+impl syn::parse::Parse for HxTrigger {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut triggers = vec![];
+
+        loop {
+            triggers.push(parse_trigger(input)?);
+
+            if input.is_empty() {
+                break;
+            }
+
+            input.parse::<Token![,]>()?;
+
+            if input.is_empty() {
+                break;
+            }
+        }
+
+        Ok(Self(triggers.join(", ")))
+    }
+}
+
+fn parse_trigger(input: ParseStream) -> syn::Result<String> {
+    if input.peek(Ident) && input.fork().parse::<Ident>()? == "every" {
+        let every = input.parse::<Ident>()?;
+        let timing = parse_timing(input)?;
+        let mut trigger = format!("every {timing}");
+        if let Some(filter) = parse_filter(input)? {
+            trigger.push_str(&filter);
+        }
+        if !input.is_empty() && !input.peek(Token![,]) {
+            return Err(syn::Error::new(
+                every.span(),
+                "`every` only supports a filter, e.g. `every 1s [isActive]`",
+            ));
+        }
+        return Ok(trigger);
+    }
+
+    let event = parse_event(input)?;
+    let mut trigger = event.clone();
+
+    if let Some(filter) = parse_filter(input)? {
+        trigger.push_str(&filter);
+    }
+
+    let mut seen: Vec<String> = vec![];
+
+    while !input.is_empty() && !input.peek(Token![,]) {
+        let modifier = input.parse::<Ident>()?;
+        let name = modifier.to_string();
+
+        if !MODIFIERS.contains(&name.as_str()) {
+            return Err(syn::Error::new(
+                modifier.span(),
+                format!(
+                    "unknown trigger modifier `{name}`, expected one of {}. \
+                     Multiple triggers are separated by commas",
+                    MODIFIERS.join(", ")
+                ),
+            ));
+        }
+
+        if seen.contains(&name) {
+            return Err(syn::Error::new(
+                modifier.span(),
+                format!("duplicate trigger modifier `{name}`"),
+            ));
+        }
+
+        if (name == "root" || name == "threshold") && event != "intersect" {
+            return Err(syn::Error::new(
+                modifier.span(),
+                format!("`{name}` is only supported by the `intersect` event"),
+            ));
+        }
+
+        let value = match name.as_str() {
+            "once" | "changed" | "consume" => None,
+            _ => {
+                input.parse::<Token![:]>()?;
+                Some(match name.as_str() {
+                    "delay" | "throttle" => parse_timing(input)?,
+                    "from" => parse_from(input)?,
+                    "target" | "root" => input.parse::<LitStr>()?.value(),
+                    "queue" => parse_queue(input)?,
+                    _ => parse_threshold(input)?,
+                })
+            }
+        };
+
+        trigger.push(' ');
+        trigger.push_str(&name);
+        if let Some(value) = value {
+            trigger.push(':');
+            trigger.push_str(&value);
+        }
+
+        seen.push(name);
+    }
+
+    Ok(trigger)
+}
+
+/// Parse an event name such as `click`, `htmx:afterSwap`, `my-event` or `"my.event"`
+fn parse_event(input: ParseStream) -> syn::Result<String> {
+    if input.peek(LitStr) {
+        let event = input.parse::<LitStr>()?;
+        if event.value().is_empty() || event.value().contains(char::is_whitespace) {
+            return Err(syn::Error::new(
+                event.span(),
+                "event names cannot be empty or contain whitespace",
+            ));
+        }
+        return Ok(event.value());
+    }
+
+    let mut event = input
+        .parse::<Ident>()
+        .map_err(|e| syn::Error::new(e.span(), "expected an event name, e.g. `click`"))?
+        .to_string();
+
+    loop {
+        let separator = if input.peek(Token![:]) && !input.peek(Token![::]) {
+            input.parse::<Token![:]>()?;
+            ':'
+        } else if input.peek(Token![-]) {
+            input.parse::<Token![-]>()?;
+            '-'
+        } else if input.peek(Token![.]) {
+            input.parse::<Token![.]>()?;
+            '.'
+        } else {
+            break;
+        };
+
+        event.push(separator);
+        event.push_str(&input.parse::<Ident>()?.to_string());
+    }
+
+    Ok(event)
+}
+
+/// Parse an event filter such as `[ctrlKey]` or `["key == 'Enter'"]`
+fn parse_filter(input: ParseStream) -> syn::Result<Option<String>> {
+    if !input.peek(syn::token::Bracket) {
+        return Ok(None);
+    }
+
+    let content;
+    let brackets = syn::bracketed!(content in input);
+
+    let filter = if content.peek(LitStr) && content.fork().parse::<LitStr>().is_ok() {
+        let lit = content.parse::<LitStr>()?;
+        if !content.is_empty() {
+            return Err(content.error("expected a single string literal filter"));
+        }
+        lit.value()
+    } else {
+        content.parse::<proc_macro2::TokenStream>()?.to_string()
+    };
+
+    if filter.trim().is_empty() {
+        return Err(syn::Error::new(
+            brackets.span.join(),
+            "event filters cannot be empty",
+        ));
+    }
+
+    Ok(Some(format!("[{filter}]")))
+}
+
+/// Parse the value of `from`, e.g. `document`, `"#btn"` or `closest "form"`
+fn parse_from(input: ParseStream) -> syn::Result<String> {
+    if input.peek(LitStr) {
+        return Ok(input.parse::<LitStr>()?.value());
+    }
+
+    let ident = input.parse::<Ident>()?;
+
+    match ident.to_string().as_str() {
+        "document" | "window" => Ok(ident.to_string()),
+        "next" | "previous" => match input.peek(LitStr) {
+            true => Ok(format!("{ident} {}", input.parse::<LitStr>()?.value())),
+            false => Ok(ident.to_string()),
+        },
+        "closest" | "find" => Ok(format!("{ident} {}", input.parse::<LitStr>()?.value())),
+        _ => Err(syn::Error::new(
+            ident.span(),
+            r##"expected `document`, `window`, `closest "sel"`, `find "sel"`, `next`, `previous` or a selector, e.g. `"#btn"`"##,
+        )),
+    }
+}
+
+fn parse_queue(input: ParseStream) -> syn::Result<String> {
+    let queue = input.parse::<Ident>()?;
+
+    match queue.to_string().as_str() {
+        "first" | "last" | "all" | "none" => Ok(queue.to_string()),
+        _ => Err(syn::Error::new(
+            queue.span(),
+            "expected one of `first`, `last`, `all` or `none`",
+        )),
+    }
+}
+
+/// Parse the `threshold` of `intersect`, a number between 0 and 1
+fn parse_threshold(input: ParseStream) -> syn::Result<String> {
+    let lit = input.parse::<Lit>()?;
+
+    let value = match lit {
+        Lit::Float(ref float) => float.base10_parse::<f64>()?,
+        Lit::Int(ref int) => int.base10_parse::<f64>()?,
+        _ => {
+            return Err(syn::Error::new(
+                lit.span(),
+                "expected a number between 0 and 1",
+            ))
+        }
+    };
+
+    if !(0.0..=1.0).contains(&value) {
+        return Err(syn::Error::new(
+            lit.span(),
+            "expected a number between 0 and 1",
+        ));
+    }
+
+    Ok(value.to_string())
+}
@@ -0,0 +1,102 @@
+//! Parsing of `#[hx_vals(...)]` and `#[hx_headers(...)]`, e.g. `#[hx_vals(id, "page-no" = page)]`
+//! or `#[hx_vals(serde = filter)]`.
+//!
+//! The values are serialized to JSON at runtime and require the `serde` feature.
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{parse::ParseStream, spanned::Spanned, Ident, LitStr, Member, Token};
+
+use crate::markup::{member_name, Markup};
+
+/// Fields serialized to a JSON object in an attribute
+#[derive(Debug)]
+pub enum JsonValues {
+    /// The listed fields become the keys of the object
+    Fields(Vec<(String, Member)>),
+
+    /// The field is serialized as is
+    Serde(Member),
+}
+
+impl JsonValues {
+    /// Push the attribute with the serialized values to the markup
+    pub fn write_to(&self, markup: &mut Markup, attribute: &str) {
+        let json: TokenStream = match self {
+            JsonValues::Fields(fields) => {
+                let entries = fields.iter().map(|(key, member)| {
+                    let field = markup.access(member);
+                    quote!((#key, &#field as &dyn htmxpress::json::erased::Serialize))
+                });
+                quote!(htmxpress::json::Json(htmxpress::json::JsonObject(&[#(#entries),*])))
+            }
+            JsonValues::Serde(member) => {
+                let field = markup.access(member);
+                quote!(htmxpress::json::Json(&#field))
+            }
+        };
+
+        markup.push_str(&format!(r#" {attribute}=""#));
+        markup.push_write(
+            quote!(write!(html, "{}", htmxpress::escape::Attr(#json))),
+            2,
+            1,
+        );
+        markup.push_str(r#"""#);
+    }
+}
+
+impl syn::parse::Parse for JsonValues {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        if input.is_empty() {
+            return Err(input.error("expected fields, e.g. `hx_vals(id, page)`"));
+        }
+
+        if input.peek(Ident) && input.peek2(Token![=]) {
+            let serde = input.parse::<Ident>()?;
+            if serde != "serde" {
+                return Err(syn::Error::new(
+                    serde.span(),
+                    r#"expected `serde = field` or `"key" = field`"#,
+                ));
+            }
+            input.parse::<Token![=]>()?;
+            let member = input.parse::<Member>()?;
+            if !input.is_empty() {
+                return Err(input.error("`serde = field` cannot be combined with other fields"));
+            }
+            return Ok(Self::Serde(member));
+        }
+
+        let mut fields: Vec<(String, Member)> = vec![];
+
+        loop {
+            let (key, span, member) = if input.peek(LitStr) {
+                let key = input.parse::<LitStr>()?;
+                input.parse::<Token![=]>()?;
+                (key.value(), key.span(), input.parse::<Member>()?)
+            } else {
+                let member = input.parse::<Member>()?;
+                (member_name(&member), member.span(), member)
+            };
+
+            if fields.iter().any(|(k, _)| *k == key) {
+                return Err(syn::Error::new(span, format!("duplicate key `{key}`")));
+            }
+
+            fields.push((key, member));
+
+            if input.is_empty() {
+                break;
+            }
+
+            input.parse::<Token![,]>()?;
+
+            if input.is_empty() {
+                break;
+            }
+        }
+
+        Ok(Self::Fields(fields))
+    }
+}